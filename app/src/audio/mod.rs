@@ -13,6 +13,7 @@ pub fn game_music_to_audio(music: Music) -> Audio {
         Music::Gameplay2 => Audio::Gameplay2,
         Music::Gameplay3 => Audio::Gameplay3,
         Music::Gameplay4 => Audio::Gameplay4,
+        Music::Reactor => Audio::Reactor,
     }
 }
 
@@ -25,6 +26,7 @@ pub enum Audio {
     Gameplay2,
     Gameplay3,
     Gameplay4,
+    Reactor,
     EndTextSad,
     EndTextHappy,
     SoundEffect(SoundEffect),
@@ -45,6 +47,7 @@ impl AudioTable {
                 Audio::Gameplay2 => audio_player.load_sound(&GAMEPLAY2),
                 Audio::Gameplay3 => audio_player.load_sound(&GAMEPLAY3),
                 Audio::Gameplay4 => audio_player.load_sound(&GAMEPLAY4),
+                Audio::Reactor => audio_player.load_sound(&REACTOR),
 
                 Audio::EndTextSad => audio_player.load_sound(&END_TEXT_SAD),
                 Audio::EndTextHappy => audio_player.load_sound(&END_TEXT_HAPPY),
@@ -10,8 +10,11 @@ use std::collections::BTreeMap;
 pub enum AppInput {
     Get,
     Wait,
+    Search,
     Examine,
     Descend,
+    Interact,
+    Flashlight,
     Slot(RangedWeaponSlot),
     Direction(CardinalDirection),
 }
@@ -28,8 +31,11 @@ impl Default for Controls {
             // Action Keys
             KeyboardInput::Char('g') => AppInput::Get,
             KeyboardInput::Char(' ') => AppInput::Wait,
+            KeyboardInput::Char('z') => AppInput::Search,
             KeyboardInput::Char('.') => AppInput::Descend,
             KeyboardInput::Char('x') => AppInput::Examine,
+            KeyboardInput::Char('e') => AppInput::Interact,
+            KeyboardInput::Char('f') => AppInput::Flashlight,
 
             // Movement Keys
             KeyboardInput::Up => AppInput::Direction(CardinalDirection::North),
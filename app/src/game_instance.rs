@@ -51,9 +51,13 @@ impl GameInstance {
                 string: "Gotta get to the reactor ...".to_string(),
             }
         } else {
+            let string = match self.scope.level_name() {
+                Some(name) => format!("Floor {}/{}: {}", current_floor, final_floor, name),
+                None => format!("Floor {}/{}", current_floor, final_floor),
+            };
             StyledString {
                 style: Style::new().with_foreground(Rgba32::new_grey(255)).with_bold(true),
-                string: format!("Floor {}/{}", current_floor, final_floor),
+                string,
             }
         }
     }
@@ -267,7 +267,7 @@ pub fn game_loop_component(initial_state: GameLoopState) -> AppCF<()> {
                 }
             }),
             Playing(witness) => match witness {
-                GameState::Win => win().map_val(|| MainMenu).continue_(),
+                GameState::Win(ending) => win(ending).map_val(|| MainMenu).continue_(),
                 GameState::GameOver => game_over().map_val(|| MainMenu).continue_(),
                 GameState::Prompt(prompt_witness) => prompt(prompt_witness).map(Playing).continue_(),
                 GameState::Running(running) => game_instance_component(running).continue_(),
@@ -278,6 +278,7 @@ pub fn game_loop_component(initial_state: GameLoopState) -> AppCF<()> {
                     try_get_melee_weapon(melee_witness).map(Playing).continue_()
                 }
                 GameState::FireWeapon(fire_witness) => fire_weapon(fire_witness).map(Playing).continue_(),
+                GameState::Interact(interact_witness) => interact(interact_witness).map(Playing).continue_(),
                 GameState::Upgrade(upgrade) => try_upgrade_component(upgrade).map(Playing).continue_(),
             },
             Examine(running) => {
@@ -0,0 +1,32 @@
+use gridbugs::chargrid::text::StyledString;
+
+use crate::prelude::*;
+
+use super::GameExamineWithMouseComponent;
+
+pub fn interact(witness: Interact) -> AppCF<GameState> {
+    on_state_then(move |state: &mut State| {
+        state.context_message = Some(StyledString {
+            string: "Interact in which direction? (escape/start to cancel)".to_string(),
+            style: Style::plain_text().with_bold(true).with_foreground(Rgba32::hex_rgb(0xFF0000)),
+        });
+        on_input_state(move |input, state: &mut State| state.controls.get_direction(input))
+            .catch_escape_or_start()
+            .overlay(GameExamineWithMouseComponent, 10)
+            .and_then(|direction_or_err| {
+                on_state(move |state: &mut State| {
+                    state.context_message = None;
+                    match direction_or_err {
+                        Err(_escape_or_start) => witness.cancel(),
+                        Ok(direction) => {
+                            let (witness, action_result) = witness.commit(state.scope_mut(), direction);
+                            if let Err(action_error) = action_result {
+                                state.context_message = Some(action_error_message(action_error));
+                            }
+                            witness
+                        }
+                    }
+                })
+            })
+    })
+}
@@ -0,0 +1,33 @@
+use super::*;
+
+fn codex_menu(entries: &[LoreEntry]) -> AppCF<usize> {
+    let mut builder = menu_builder().vi_keys();
+    for (index, entry) in entries.iter().enumerate() {
+        let title = entry.title.clone();
+        let identifier = MENU_FADE_SPEC.identifier(move |b| write!(b, "{}", title).unwrap());
+        builder.add_item_mut(item(index, identifier));
+    }
+    builder.build_cf()
+}
+
+/// Lists every log the player has read so far, and lets them read any of them again
+pub fn codex() -> AppCF<()> {
+    on_state_then(|state: &mut State| {
+        let instance = state.instance.as_ref().unwrap();
+        let entries = instance.scope.codex();
+        if entries.is_empty() {
+            return popup("You haven't read any logs yet.".to_string());
+        }
+
+        let title = StyledString { string: "Codex".to_string(), style: Style::plain_text() };
+        menu_style(codex_menu(&entries).with_title(cf(title).ignore_state(), 1)).menu_harness().and_then(
+            move |result| match result {
+                Ok(index) => {
+                    let LoreEntry { title, pages } = entries[index].clone();
+                    read_pages(Some(title), pages)
+                }
+                Err(_close) => val_once(()),
+            },
+        )
+    })
+}
@@ -55,7 +55,11 @@ pub fn main_menu_loop() -> AppCF<MainMenuOutput> {
         Options => title_decorate(options_menu()).continue_(),
         Help => text::help(MAIN_MENU_TEXT_WIDTH).centre().continue_(),
         Prologue => text::prologue(MAIN_MENU_TEXT_WIDTH).centre().continue_(),
-        Epilogue => text::epilogue(MAIN_MENU_TEXT_WIDTH).centre().continue_(),
+        Epilogue => {
+            on_state_then(|state: &mut State| text::epilogue(MAIN_MENU_TEXT_WIDTH, state.config.ending))
+                .centre()
+                .continue_()
+        }
         Quit => val_once(MainMenuOutput::Quit).break_(),
     })
 }
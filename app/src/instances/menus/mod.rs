@@ -1,14 +1,18 @@
 use super::*;
 use gridbugs::chargrid::border::BorderStyle;
 
+mod codex;
 mod main_menu;
+mod objectives;
 mod options;
 mod paused;
 mod prologue;
 mod prompt;
 mod upgrade;
 
+pub use codex::*;
 pub use main_menu::*;
+pub use objectives::*;
 pub use options::*;
 pub use paused::*;
 pub use prologue::*;
@@ -0,0 +1,25 @@
+use super::*;
+
+/// Lists the optional objectives on the current floor along with their rewards
+pub fn objectives() -> AppCF<()> {
+    on_state_then(|state: &mut State| {
+        let instance = state.instance.as_ref().unwrap();
+        let objectives = instance.scope.objectives();
+        if objectives.is_empty() {
+            return popup("There are no objectives on this floor.".to_string());
+        }
+
+        let lines = objectives
+            .iter()
+            .map(|objective| {
+                let check = if objective.complete { "[x]" } else { "[ ]" };
+                let reward = match objective.reward {
+                    Reward::Credit(amount) => format!("${}", amount),
+                    Reward::Upgrade => "a free upgrade".to_string(),
+                };
+                format!("{} {} ({})", check, objective_name(objective.typ), reward)
+            })
+            .collect::<Vec<_>>();
+        popup(format!("Objectives\n\n{}", lines.join("\n")))
+    })
+}
@@ -6,6 +6,8 @@ enum PauseMenuEntry {
     SaveQuit,
     Save,
     Options,
+    Objectives,
+    Codex,
     Help,
     Clear,
 }
@@ -29,6 +31,8 @@ fn pause_menu() -> AppCF<PauseMenuEntry> {
     add_item(SaveQuit, "Save and Quit", 'q');
     add_item(Save, "Save", 's');
     add_item(Options, "Options", 'o');
+    add_item(Objectives, "Objectives", 'b');
+    add_item(Codex, "Codex", 'x');
     add_item(Help, "Help", 'h');
     add_item(Clear, "Clear", 'c');
     builder.build_cf()
@@ -51,6 +55,8 @@ pub fn pause_menu_loop(running: state::Running) -> AppCF<PauseOutput> {
                 })
                 .break_(),
                 Options => options_menu().continue_with(running),
+                Objectives => objectives().continue_with(running),
+                Codex => codex().continue_with(running),
                 Help => text::help(text_width).continue_with(running),
                 Clear => on_state(|state: &mut State| {
                     state.clear_saved_game();
@@ -6,7 +6,27 @@ pub fn prompt(prompt_witness: Prompt) -> AppCF<GameState> {
     on_state_then(move |state: &mut State| {
         state.examine_message = None;
         state.cursor = None;
-        popup(prompt_witness.message().to_string()).map_val(|| prompt_witness.running())
+        read_pages(prompt_witness.title().map(str::to_string), prompt_witness.pages().to_vec())
+            .map_val(|| prompt_witness.running())
+    })
+}
+
+/// Shows each page in turn in a popup, headed by the title if there is one
+pub fn read_pages(title: Option<String>, pages: Vec<String>) -> AppCF<()> {
+    let num_pages = pages.len();
+    loop_(0, move |index| {
+        let mut text = title.as_ref().map_or_else(String::new, |title| format!("{}\n\n", title));
+        text.push_str(&pages[index]);
+        if num_pages > 1 {
+            text.push_str(&format!("\n\n(page {}/{})", index + 1, num_pages));
+        }
+        popup(text).map(move |()| {
+            if index + 1 < num_pages {
+                LoopControl::Continue(index + 1)
+            } else {
+                LoopControl::Break(())
+            }
+        })
     })
 }
 
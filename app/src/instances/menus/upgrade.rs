@@ -7,16 +7,7 @@ use super::menu_style;
 use crate::{instances::popup, prelude::*};
 
 fn upgrade_identifier(upgrade: Upgrade) -> String {
-    let name = match upgrade.typ {
-        UpgradeType::Toughness => "Toughness",
-    };
-    let level = match upgrade.level {
-        UpgradeLevel::Level1 => "1",
-        UpgradeLevel::Level2 => "2",
-        UpgradeLevel::Level3 => "3",
-    };
-    let price = upgrade.level.cost();
-    format!("{} {} (${})", name, level, price)
+    format!("{} (${})", text::upgrade_name(upgrade), upgrade.level.cost())
 }
 
 fn upgrade_description(upgrade: &Upgrade) -> &'static str {
@@ -29,6 +20,27 @@ fn upgrade_description(upgrade: &Upgrade) -> &'static str {
         Upgrade { typ: Toughness, level: Level3 } => {
             "Toughness 3: Immune to Explosions + Explosive Rounds ;)"
         }
+        Upgrade { typ: Accuracy, level: Level1 } => {
+            "Accuracy 1: Steady Aim\nHalve the chance that your shots breach the hull."
+        }
+        Upgrade { typ: Accuracy, level: Level2 | Level3 } => {
+            "Accuracy 2: Killer Instinct\nDeal double melee damage."
+        }
+        Upgrade { typ: Endurance, level: Level1 } => {
+            "Endurance 1: Braced\nThe vacuum drags you half as often."
+        }
+        Upgrade { typ: Endurance, level: Level2 | Level3 } => {
+            "Endurance 2: Deep Lungs\nDouble your oxygen supply."
+        }
+        Upgrade { typ: Sensors, level: Level1 } => {
+            "Sensors 1: Motion Tracker\nShows robots that moved last turn, even through walls."
+        }
+        Upgrade { typ: Sensors, level: Level2 } => {
+            "Sensors 2: Wide Band\nIncrease the motion tracker's range."
+        }
+        Upgrade { typ: Sensors, level: Level3 } => {
+            "Sensors 3: Deep Scan\nIncrease the motion tracker's range further."
+        }
     }
 }
 
@@ -8,6 +8,7 @@ use gridbugs::chargrid::{
 
 mod examine;
 mod gameover;
+mod interact;
 mod menus;
 mod playing;
 mod weapon;
@@ -15,6 +16,7 @@ mod win;
 
 pub use examine::*;
 pub use gameover::*;
+pub use interact::*;
 pub use menus::*;
 pub use playing::*;
 pub use weapon::*;
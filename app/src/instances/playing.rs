@@ -42,9 +42,12 @@ impl GameInstanceComponent {
                         AppInput::Get => running.player_get(&mut instance.scope),
                         AppInput::Examine => return GameLoopState::Examine(running),
                         AppInput::Wait => (running.player_wait(&mut instance.scope), Ok(())),
+                        AppInput::Search => (running.player_search(&mut instance.scope), Ok(())),
                         AppInput::Slot(slot) => running.player_fire_weapon(&instance.scope, slot),
                         AppInput::Direction(direction) => running.player_walk(&mut instance.scope, direction),
                         AppInput::Descend => running.player_descend(&mut instance.scope),
+                        AppInput::Interact => running.player_interact(&instance.scope),
+                        AppInput::Flashlight => running.player_toggle_flashlight(&mut instance.scope),
                     };
 
                     if let Err(action_error) = action_result {
@@ -42,6 +42,8 @@ pub use prelude::*;
 #[derive(Debug, Clone, Copy, Serialize, Deserialize)]
 pub struct AppConfig {
     won: bool,
+    #[serde(default)]
+    ending: Ending,
     sfx_volume: f32,
     first_run: bool,
     music_volume: f32,
@@ -49,7 +51,7 @@ pub struct AppConfig {
 
 impl Default for AppConfig {
     fn default() -> Self {
-        Self { music_volume: 0.2, sfx_volume: 0.5, won: false, first_run: true }
+        Self { music_volume: 0.2, sfx_volume: 0.5, won: false, ending: Ending::default(), first_run: true }
     }
 }
 
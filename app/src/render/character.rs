@@ -1,5 +1,5 @@
-use forgotten_game::{Coord, NpcType, StateScope};
-use gridbugs::chargrid::prelude::RenderCell;
+use forgotten_game::{CardinalDirection, Clearance, Coord, NpcType, StateScope, Theme};
+use gridbugs::chargrid::prelude::{RenderCell, Rgba32};
 
 use crate::{color::*, Tile};
 
@@ -22,41 +22,155 @@ pub fn npc_renderable(tile: Tile, remembered: bool) -> RenderCell {
                 NpcType::DoomBot => {
                     RenderCell::BLANK.with_character('Œ').with_foreground(DOOMBOT).with_bold(true)
                 }
+                NpcType::RepairBot => {
+                    RenderCell::BLANK.with_character('ŕ').with_foreground(REPAIRBOT).with_bold(true)
+                }
+                NpcType::ShieldBot => {
+                    RenderCell::BLANK.with_character('Ş').with_foreground(SHIELDBOT).with_bold(true)
+                }
+                NpcType::Drone => {
+                    RenderCell::BLANK.with_character('ẟ').with_foreground(DRONE).with_bold(true)
+                }
+                NpcType::Skimmer => {
+                    RenderCell::BLANK.with_character('ş').with_foreground(SKIMMER).with_bold(true)
+                }
+                NpcType::Turret => {
+                    RenderCell::BLANK.with_character('Ŧ').with_foreground(TURRET).with_bold(true)
+                }
             },
+            // Hacked robots keep their glyph but switch to the allied colour
+            Tile::AlliedNpc(npc_type) => {
+                npc_renderable(Tile::Npc(npc_type), remembered).with_foreground(ALLIED)
+            }
 
             _ => unreachable!("npc_renderable called with non-npc tile"),
         }
     }
 }
 
+pub const fn clearance_colour(clearance: Clearance) -> Rgba32 {
+    match clearance {
+        Clearance::Blue => CLEARANCE_BLUE,
+        Clearance::Yellow => CLEARANCE_YELLOW,
+        Clearance::Red => CLEARANCE_RED,
+    }
+}
+
 pub fn terrain_renderable(scope: &StateScope, tile: Tile, coord: Coord) -> RenderCell {
     match tile {
         Tile::DoorClosed => {
             RenderCell::BLANK.with_character('+').with_background(LIGHT_GREY).with_foreground(WHITE)
         }
+        Tile::DoorLocked(clearance) => RenderCell::BLANK
+            .with_character('+')
+            .with_background(clearance_colour(clearance))
+            .with_foreground(WHITE)
+            .with_bold(true),
         Tile::DoorOpen => {
             RenderCell::BLANK.with_character('-').with_background(LIGHT_GREY).with_foreground(WHITE)
         }
-        Tile::Floor | Tile::CaveFloor | Tile::Water | Tile::Grass | Tile::GrassCrushed => {
-            floor_renderable(tile)
+        Tile::NarrowDoorClosed => {
+            RenderCell::BLANK.with_character('+').with_background(NARROW_DOOR_BG).with_foreground(LIGHT_GREY)
+        }
+        Tile::NarrowDoorOpen => {
+            RenderCell::BLANK.with_character('-').with_background(NARROW_DOOR_BG).with_foreground(LIGHT_GREY)
         }
+        Tile::Floor
+        | Tile::CaveFloor
+        | Tile::Water
+        | Tile::Chasm
+        | Tile::Bridge
+        | Tile::Grass
+        | Tile::GrassCrushed => floor_renderable(tile),
         Tile::Wall | Tile::CaveWall => {
             let is_wall_below = scope.0.is_wall_known_at(coord + Coord::new(0, 1));
-            wall_renderable(tile, is_wall_below)
+            wall_renderable(tile, is_wall_below, scope.theme())
         }
         Tile::Reactor => RenderCell::BLANK.with_character('☼').with_foreground(REACTOR),
+        Tile::ShieldGenerator => {
+            RenderCell::BLANK.with_character('Ω').with_foreground(SHIELD_GENERATOR).with_bold(true)
+        }
         Tile::Stairs => RenderCell::BLANK.with_character('>').with_foreground(STAIRS),
+        Tile::Elevator => RenderCell::BLANK.with_character('≡').with_foreground(ELEVATOR).with_bold(true),
+        Tile::Fire => RenderCell::BLANK.with_character('^').with_foreground(FIRE_FG).with_background(FIRE_BG),
+        Tile::Rubble => RenderCell::BLANK.with_character('%').with_foreground(RUBBLE),
+        Tile::HullBreach => RenderCell::BLANK
+            .with_character('*')
+            .with_foreground(HULL_BREACH)
+            .with_background(SPACE_BACKGROUND),
+        Tile::Camera(facing) => {
+            let ch = match facing {
+                CardinalDirection::North => '▲',
+                CardinalDirection::East => '►',
+                CardinalDirection::South => '▼',
+                CardinalDirection::West => '◄',
+            };
+            RenderCell::BLANK.with_character(ch).with_background(WHITE).with_foreground(CAMERA)
+        }
+        Tile::AlarmPanel => RenderCell::BLANK
+            .with_character('!')
+            .with_background(WHITE)
+            .with_foreground(ALARM_PANEL)
+            .with_bold(true),
+        Tile::Terminal => RenderCell::BLANK
+            .with_character('▣')
+            .with_background(WHITE)
+            .with_foreground(TERMINAL)
+            .with_bold(true),
+        Tile::PowerRelay => RenderCell::BLANK
+            .with_character('ϟ')
+            .with_background(WHITE)
+            .with_foreground(POWER_RELAY)
+            .with_bold(true),
+        Tile::Generator => RenderCell::BLANK
+            .with_character('Φ')
+            .with_background(Rgba32::new_grey(40))
+            .with_foreground(GENERATOR)
+            .with_bold(true),
+        Tile::PowerSocket => RenderCell::BLANK
+            .with_character('¤')
+            .with_background(WHITE)
+            .with_foreground(POWER_SOCKET)
+            .with_bold(true),
+        Tile::ServerRack => RenderCell::BLANK
+            .with_character('▤')
+            .with_background(Rgba32::new_grey(40))
+            .with_foreground(SERVER_RACK)
+            .with_bold(true),
         _ => unreachable!("Tried to render a non-terrain tile as terrain: {:?}", tile),
     }
 }
 
-pub fn wall_renderable(tile: Tile, is_wall_below: bool) -> RenderCell {
+// Room walls are textured and coloured to match the theme of the floor. The face is drawn when
+// there's more wall below, and the edge is the strip drawn along the bottom of a wall.
+struct WallStyle {
+    face: char,
+    background: Rgba32,
+    edge: Rgba32,
+}
+
+const fn wall_style(theme: Option<Theme>) -> WallStyle {
+    match theme {
+        None => WallStyle { face: ' ', background: WHITE, edge: LIGHT_GREY },
+        Some(Theme::Barracks) => WallStyle { face: ' ', background: BARRACKS_WALL, edge: BARRACKS_WALL_EDGE },
+        Some(Theme::Cistern) => WallStyle { face: '░', background: CISTERN_WALL, edge: CISTERN_WALL_EDGE },
+        Some(Theme::Hydroponics) => {
+            WallStyle { face: '┼', background: HYDROPONICS_WALL, edge: HYDROPONICS_WALL_EDGE }
+        }
+        Some(Theme::ReactorCore) => {
+            WallStyle { face: '≡', background: REACTOR_CORE_WALL, edge: REACTOR_CORE_WALL_EDGE }
+        }
+    }
+}
+
+pub fn wall_renderable(tile: Tile, is_wall_below: bool, theme: Option<Theme>) -> RenderCell {
     match tile {
         Tile::Wall => {
+            let WallStyle { face, background, edge } = wall_style(theme);
             if is_wall_below {
-                RenderCell::BLANK.with_character(' ').with_background(WHITE)
+                RenderCell::BLANK.with_character(face).with_background(background).with_foreground(edge)
             } else {
-                RenderCell::BLANK.with_character('▄').with_background(WHITE).with_foreground(LIGHT_GREY)
+                RenderCell::BLANK.with_character('▄').with_background(background).with_foreground(edge)
             }
         }
         Tile::CaveWall => {
@@ -82,6 +196,10 @@ pub fn floor_renderable(tile: Tile) -> RenderCell {
         Tile::Water => {
             RenderCell::BLANK.with_character('≈').with_foreground(WATER_FG).with_background(WATER_BG)
         }
+        Tile::Chasm => {
+            RenderCell::BLANK.with_character('·').with_foreground(CHASM_FG).with_background(CHASM_BG)
+        }
+        Tile::Bridge => RenderCell::BLANK.with_character('=').with_foreground(BRIDGE),
         _ => unreachable!("floor_renderable called on non-wall tile"),
     }
 }
@@ -118,6 +236,10 @@ pub fn item_renderable(tile: Tile) -> RenderCell {
             .with_foreground(HEALTH)
             .with_background(MEDKIT_TOP)
             .with_bold(true),
+        Tile::Keycard(clearance) => {
+            RenderCell::BLANK.with_character('⌐').with_foreground(clearance_colour(clearance)).with_bold(true)
+        }
+        Tile::DogTags => RenderCell::BLANK.with_character('ŧ').with_foreground(DOG_TAGS).with_bold(true),
         Tile::Upgrade => RenderCell::BLANK
             .with_character('Ū')
             .with_foreground(UPGRADE_FOREGROUND)
@@ -18,16 +18,49 @@ pub const LIGHT_RED: Rgba32 = HEALTH.saturating_scalar_mul_div(2, 3);
 pub const FLOOR: Rgba32 = LIGHT_GREY;
 pub const DOOR_FG: Rgba32 = LIGHT_GREY;
 pub const DOOR_BG: Rgba32 = WHITE;
+pub const NARROW_DOOR_BG: Rgba32 = Rgba32::new_grey(63);
 pub const GRASS: Rgba32 = Rgba32::new_rgb(0, 187, 63);
 pub const GRASS_CRUSHED: Rgba32 = Rgba32::new_rgb(0, 127, 63);
 pub const CAVE_WALL_FG: Rgba32 = Rgba32::new_rgb(68, 39, 14);
 pub const CAVE_WALL_BG: Rgba32 = Rgba32::new_rgb(125, 82, 44);
 pub const ROOM_WALL_FG: Rgba32 = WHITE;
 pub const ROOM_WALL_BG: Rgba32 = LIGHT_GREY;
+pub const BARRACKS_WALL: Rgba32 = Rgba32::new_rgb(187, 187, 159);
+pub const BARRACKS_WALL_EDGE: Rgba32 = Rgba32::new_rgb(95, 95, 63);
+pub const CISTERN_WALL: Rgba32 = Rgba32::new_rgb(143, 167, 187);
+pub const CISTERN_WALL_EDGE: Rgba32 = Rgba32::new_rgb(47, 79, 111);
+pub const HYDROPONICS_WALL: Rgba32 = Rgba32::new_rgb(207, 231, 199);
+pub const HYDROPONICS_WALL_EDGE: Rgba32 = Rgba32::new_rgb(47, 127, 47);
+pub const REACTOR_CORE_WALL: Rgba32 = Rgba32::new_rgb(175, 143, 127);
+pub const REACTOR_CORE_WALL_EDGE: Rgba32 = Rgba32::new_rgb(127, 47, 15);
 pub const WATER_FG: Rgba32 = Rgba32::new_rgb(0, 127, 187);
 pub const WATER_BG: Rgba32 = Rgba32::new_rgb(0, 63, 127);
+pub const CHASM_FG: Rgba32 = Rgba32::new_rgb(47, 39, 63);
+pub const CHASM_BG: Rgba32 = Rgba32::new_rgb(7, 5, 11);
+pub const BRIDGE: Rgba32 = Rgba32::new_rgb(159, 111, 63);
 pub const REACTOR: Rgba32 = Rgba32::new_rgb(255, 132, 0);
 pub const STAIRS: Rgba32 = Rgba32::new_rgb(0, 255, 0);
+pub const SHIELD_GENERATOR: Rgba32 = Rgba32::new_rgb(0, 127, 255);
+pub const CLEARANCE_BLUE: Rgba32 = Rgba32::new_rgb(31, 95, 255);
+pub const CLEARANCE_YELLOW: Rgba32 = Rgba32::new_rgb(255, 219, 0);
+pub const CLEARANCE_RED: Rgba32 = Rgba32::new_rgb(219, 0, 0);
+pub const ELEVATOR: Rgba32 = Rgba32::new_rgb(0, 255, 127);
+pub const FIRE_FG: Rgba32 = Rgba32::new_rgb(255, 219, 31);
+pub const FIRE_BG: Rgba32 = Rgba32::new_rgb(187, 47, 0);
+pub const RUBBLE: Rgba32 = Rgba32::new_grey(127);
+pub const CAMERA: Rgba32 = Rgba32::new_rgb(187, 0, 0);
+pub const ALARM_PANEL: Rgba32 = Rgba32::new_rgb(255, 0, 0);
+pub const TERMINAL: Rgba32 = Rgba32::new_rgb(0, 140, 200);
+pub const POWER_RELAY: Rgba32 = Rgba32::new_rgb(255, 200, 0);
+pub const GENERATOR: Rgba32 = Rgba32::new_rgb(255, 160, 0);
+pub const SERVER_RACK: Rgba32 = Rgba32::new_rgb(0, 200, 80);
+pub const FLASHLIGHT: Rgba32 = Rgba32::new_rgb(255, 244, 214);
+pub const POWER_SOCKET: Rgba32 = Rgba32::new_rgb(0, 200, 255);
+pub const OBJECTIVE: Rgba32 = Rgba32::new_rgb(255, 200, 0);
+pub const DOG_TAGS: Rgba32 = Rgba32::new_grey(200);
+pub const HULL_BREACH: Rgba32 = Rgba32::new_rgb(200, 220, 255);
+pub const MOTION_TRACKER: Rgba32 = Rgba32::new_rgb(0, 160, 60);
+pub const MOTION_BLIP: Rgba32 = Rgba32::new_rgb(127, 255, 127);
 
 // Entity Colors
 pub const PLAYER: Rgba32 = YELLOW;
@@ -35,6 +68,12 @@ pub const MINIBOT: Rgba32 = Rgba32::new_rgb(0, 187, 0);
 pub const SECBOT: Rgba32 = Rgba32::new_rgb(187, 0, 0);
 pub const ROBOCOP: Rgba32 = Rgba32::new_rgb(187, 0, 0);
 pub const DOOMBOT: Rgba32 = Rgba32::new_rgb(187, 0, 0);
+pub const REPAIRBOT: Rgba32 = Rgba32::new_rgb(0, 255, 127);
+pub const SHIELDBOT: Rgba32 = Rgba32::new_rgb(0, 187, 255);
+pub const DRONE: Rgba32 = Rgba32::new_rgb(255, 255, 127);
+pub const SKIMMER: Rgba32 = Rgba32::new_rgb(0, 127, 255);
+pub const TURRET: Rgba32 = Rgba32::new_rgb(255, 127, 0);
+pub const ALLIED: Rgba32 = Rgba32::new_rgb(0, 255, 0);
 
 // Ability Colors
 pub const LASER: Rgba32 = Rgba32::new_rgb(0, 255, 0);
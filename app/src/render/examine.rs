@@ -11,6 +11,7 @@ pub fn examine(game: &StateScope, coord: Coord) -> Option<StyledString> {
     let mut entity_under_cursor = None;
     let visibility = &game.visibility_grid().get_visibility(coord);
     let data = &game.visibility_grid().get_data(coord);
+    let secret_door = data.map_or(false, |data| data.secret_door);
     if let Some(data) = data {
         let verb = match visibility {
             CellVisibility::Never => None,
@@ -31,7 +32,12 @@ pub fn examine(game: &StateScope, coord: Coord) -> Option<StyledString> {
     }
 
     entity_under_cursor.and_then(|(tile, verb)| {
-        tile_str(tile).map(|label| match label {
+        let label = if secret_door && matches!(tile, Tile::DoorClosed | Tile::DoorOpen) {
+            Some(TileLabel::Name("a secret door".to_string()))
+        } else {
+            tile_str(tile)
+        };
+        label.map(|label| match label {
             TileLabel::Literal(literal) => StyledString::plain_text(literal),
             TileLabel::Name(name) => {
                 let verb_str = match verb {
@@ -55,8 +61,12 @@ fn tile_str(tile: Tile) -> Option<TileLabel> {
         tile,
         Tile::Player
             | Tile::Npc(..)
+            | Tile::AlliedNpc(..)
             | Tile::DoorClosed
             | Tile::DoorOpen
+            | Tile::DoorLocked(..)
+            | Tile::NarrowDoorClosed
+            | Tile::NarrowDoorOpen
             | Tile::Floor
             | Tile::CaveFloor
             | Tile::Wall
@@ -64,15 +74,35 @@ fn tile_str(tile: Tile) -> Option<TileLabel> {
             | Tile::Grass
             | Tile::GrassCrushed
             | Tile::Water
+            | Tile::Chasm
+            | Tile::Bridge
             | Tile::Upgrade
+            | Tile::Keycard(..)
+            | Tile::Camera(..)
+            | Tile::AlarmPanel
+            | Tile::Terminal
+            | Tile::PowerRelay
+            | Tile::Generator
+            | Tile::PowerSocket
+            | Tile::ServerRack
+            | Tile::DogTags
             | Tile::Credit1
             | Tile::Credit2
             | Tile::Credit3
     )
     .then(|| TileLabel::Name(desc.clone()))
     .or_else(|| {
-        matches!(tile, Tile::Weapon(..) | Tile::Reactor | Tile::Stairs)
-            .then(|| TileLabel::Literal(desc.clone()))
+        matches!(
+            tile,
+            Tile::Weapon(..)
+                | Tile::Reactor
+                | Tile::ShieldGenerator
+                | Tile::Stairs
+                | Tile::Elevator
+                | Tile::Fire
+                | Tile::HullBreach
+        )
+        .then(|| TileLabel::Literal(desc.clone()))
     })
 }
 
@@ -81,14 +111,45 @@ fn default_tile_str(tile: Tile) -> Option<&'static str> {
         Tile::Player => "yourself",
         Tile::Floor | Tile::CaveFloor => "the floor",
         Tile::DoorClosed | Tile::DoorOpen => "a door",
+        Tile::NarrowDoorClosed | Tile::NarrowDoorOpen => "a narrow door. heavy robots can't fit through",
         Tile::Wall | Tile::CaveWall => "a wall",
         Tile::Grass => "dense patch of grass",
         Tile::GrassCrushed => "crushed grass",
         Tile::Water => "some lovely cave water",
+        Tile::Chasm => "a chasm. you can see and shoot across, but not walk",
+        Tile::Bridge => "a narrow bridge",
         Tile::Reactor => "core reactor that powers all robots",
+        Tile::ShieldGenerator => "a shield generator. the reactor can't be damaged while it runs",
         Tile::Stairs => "an elevator down...",
+        Tile::Elevator => "the extraction elevator. your way off the station",
+        Tile::Fire => "fire! it will burn anything standing in it",
+        Tile::Rubble => "the rubble of a collapsed wall",
+        Tile::HullBreach => "a breach in the hull! air is rushing out into space",
         Tile::Medkit => "a medkit",
+        Tile::DoorLocked(clearance) => match clearance {
+            Clearance::Blue => "a door locked with blue clearance",
+            Clearance::Yellow => "a door locked with yellow clearance",
+            Clearance::Red => "a door locked with red clearance",
+        },
+        Tile::Keycard(clearance) => match clearance {
+            Clearance::Blue => "a blue keycard",
+            Clearance::Yellow => "a yellow keycard",
+            Clearance::Red => "a red keycard",
+        },
         Tile::Upgrade => "an upgrade store",
+        Tile::AlarmPanel => "an alarm panel. RoboCops use it to call every robot on the floor",
+        Tile::Terminal => "a terminal. Someone left a log on it",
+        Tile::PowerRelay => "a power relay feeding the floor's security systems",
+        Tile::Generator => "a power generator. Destroying it blacks out this wing of the floor",
+        Tile::PowerSocket => "a power socket. Walk into it to recharge your flashlight",
+        Tile::ServerRack => "a server rack humming away in the server room",
+        Tile::DogTags => "the dog tags of one of the forward team",
+        Tile::Camera(facing) => match facing {
+            CardinalDirection::North => "a security camera watching to the north",
+            CardinalDirection::East => "a security camera watching to the east",
+            CardinalDirection::South => "a security camera watching to the south",
+            CardinalDirection::West => "a security camera watching to the west",
+        },
 
         Tile::Credit1 => "a $1 credit chip",
         Tile::Credit2 => "a $2 credit chip",
@@ -110,7 +171,13 @@ fn default_tile_str(tile: Tile) -> Option<&'static str> {
             NpcType::SecBot => "a sec-bot. an upgraded bot from mini-bot.",
             NpcType::RoboCop => "a robo-cop. a security bot for the <blank>",
             NpcType::DoomBot => "a doom-bot. its only purpose is to kill",
+            NpcType::RepairBot => "a repair-bot. it patches up damaged robots and runs from trouble",
+            NpcType::ShieldBot => "a shield-bot. robots next to it are much harder to damage",
+            NpcType::Drone => "a drone. it flies over water and grass",
+            NpcType::Skimmer => "a skimmer. it lurks in the water",
+            NpcType::Turret => "a turret. it can't move, but it never misses a chance to shoot",
         },
+        Tile::AlliedNpc(_) => "a hacked robot. it fights for you until the hack wears off",
 
         Tile::Bullet => return None,
     })
@@ -44,11 +44,12 @@ impl Tint for Remembered {
 impl GameLoopData {
     pub fn render_game_with_visibility(&self, ctx: Ctx, fb: &mut FrameBuffer) {
         let ctx = ctx.add_offset(GAME_VIEW_OFFSET);
+        let ambient_light = self.scope().ambient_light();
         for (coord, visibility) in self.scope().0.enumerate_cell_visibility() {
             match visibility {
                 CellVisibility::Never => (),
                 CellVisibility::Previous(data) => {
-                    let light_colour = AMBIENT_COL;
+                    let light_colour = blackout(ambient_light, ambient_light, data);
                     self.render_cell(
                         coord,
                         data,
@@ -59,7 +60,7 @@ impl GameLoopData {
                     );
                 }
                 CellVisibility::Current { data, light_colour } => {
-                    let light_colour = light_colour.unwrap_or(AMBIENT_COL);
+                    let light_colour = blackout(light_colour.unwrap_or(ambient_light), ambient_light, data);
                     self.render_cell(
                         coord,
                         data,
@@ -115,19 +116,38 @@ impl GameLoopData {
             | Tile::Floor
             | Tile::CaveFloor
             | Tile::Water
+            | Tile::Chasm
+            | Tile::Bridge
             | Tile::Grass
             | Tile::GrassCrushed
             | Tile::DoorClosed
             | Tile::DoorOpen
+            | Tile::DoorLocked(_)
+            | Tile::NarrowDoorClosed
+            | Tile::NarrowDoorOpen
             | Tile::Reactor
+            | Tile::ShieldGenerator
+            | Tile::Camera(_)
+            | Tile::AlarmPanel
+            | Tile::Terminal
+            | Tile::PowerRelay
+            | Tile::Generator
+            | Tile::PowerSocket
+            | Tile::ServerRack
+            | Tile::Elevator
+            | Tile::Fire
+            | Tile::Rubble
+            | Tile::HullBreach
             | Tile::Stairs => terrain_renderable(self.scope(), tile, coord),
 
             // Entity
-            Tile::Player | Tile::Npc(_) => npc_renderable(tile, remembered),
+            Tile::Player | Tile::Npc(_) | Tile::AlliedNpc(_) => npc_renderable(tile, remembered),
             Tile::Bullet => RenderCell::BLANK.with_character('◊').with_background(color::BULLET),
 
             Tile::Weapon(_)
             | Tile::Medkit
+            | Tile::Keycard(_)
+            | Tile::DogTags
             | Tile::Upgrade
             | Tile::Credit1
             | Tile::Credit2
@@ -136,6 +156,15 @@ impl GameLoopData {
     }
 }
 
+// Cells without power lose the ambient light, leaving only what the player and any fires light up
+fn blackout(light_colour: Rgb24, ambient_light: Rgb24, cell: &VisibleCellData) -> Rgb24 {
+    if cell.powered {
+        light_colour
+    } else {
+        light_colour.saturating_sub(ambient_light).saturating_add(BLACKOUT_COL)
+    }
+}
+
 fn layer_depth(layer: Layer) -> i8 {
     match layer {
         Layer::Floor => 0,
@@ -59,13 +59,58 @@ pub fn help(width: u32) -> AppCF<()> {
             plain("Minibot - basic guard robot\n"),
             plain("SecBot - upgraded minibot\n"),
             plain("RoboCop - elite guard robot. Alerts nearby robots when it sees you.\n"),
-            plain("Doom Bot - Kill bot. Very hard to kill. Explodes on death\n\n"),
+            plain("Doom Bot - Kill bot. Very hard to kill. Too bulky for narrow doors. Explodes on death\n"),
+            plain("Security Camera - reports your position to every robot on the floor\n"),
+            plain("Alarm Panel - RoboCops run to these to raise the alarm\n"),
+            plain("Repair Bot - runs away from you and repairs nearby robots\n"),
+            plain("Shield Bot - gives extra armour to the robots standing next to it\n"),
+            plain("Drone - flies over water and grass\n"),
+            plain("Skimmer - amphibious bot that likes to lurk in the water\n"),
+            plain("Turret - can't move. A shock from the cattle prod knocks it out\n\n"),
+            bold("Locked Doors\n"),
+            plain("Coloured doors need a keycard of at least that clearance. "),
+            plain("Keycards lie somewhere on the floor, and RoboCops sometimes drop theirs.\n\n"),
+            bold("The Reactor\n"),
+            plain("Destroy the shield generators(Ω) around the reactor before it can be damaged. "),
+            plain("Expect reinforcements as it breaks down. "),
+            plain("Once it is destroyed, race back to the extraction elevator(≡) before the meltdown.\n\n"),
+            bold("Objectives\n"),
+            plain("Most floors have optional objectives which pay out credits or a free upgrade. "),
+            plain("Check them in the pause menu.\n\n"),
+            bold("Power\n"),
+            plain("Each generator(Φ) powers a wing of the floor. Smash one to black out its wing: "),
+            plain("the lights go out, doors jam open and turrets shut down.\n\n"),
+            bold("Flashlight\n"),
+            plain("Your flashlight lights up a cone in the direction you last moved or fired, but robots "),
+            plain("spot you from much further away while it's on. Recharge it at a power socket(¤).\n\n"),
+            bold("Hull Breaches\n"),
+            plain("Railgun and Fifty Cal rounds can punch through the outer hull(*). Air rushes out, dragging "),
+            plain("everything nearby towards the breach. Close a door to seal it off before your oxygen runs out.\n\n"),
+            bold("Motion Tracker\n"),
+            plain("The Sensors upgrade adds a motion tracker to your HUD. It shows robots that moved "),
+            plain("last turn as blips around you, even through walls.\n\n"),
+            bold("Terminals\n"),
+            plain("Walk into a terminal(▣) to read the log left on it. "),
+            plain("Logs you have read are kept in the Codex, in the pause menu.\n\n"),
+            bold("Hacking\n"),
+            plain("Interact with a stunned robot to hack it. It fights for you for a while.\n\n"),
+            bold("Chasms\n"),
+            plain("Chasms(·) can't be walked across, but you can see and shoot over them. Drones fly "),
+            plain("straight over. Punch a robot into one barehanded, or blast it in with an explosion, "),
+            plain("to destroy it. "),
+            plain("Bridges(=) always lead across to the rest of the floor.\n\n"),
+            bold("Secret Doors\n"),
+            plain("Some walls hide doors to rooms full of loot. Search to look for them nearby, taking a turn. "),
+            plain("The closer you stand, the more likely you are to spot one. Explosions blow them open.\n\n"),
             // Keys
             bold("Default Keyboard Controls\n"),
             plain("Movement/Aim: Arrows/WASD/HJKL\n"),
             plain("Cancel Aim: Escape\n"),
             plain("Wait: Space\n"),
+            plain("Search: Z\n"),
             plain("Examine: X\n"),
+            plain("Interact (open/close doors, hack): E\n"),
+            plain("Toggle Flashlight: F\n"),
             plain("Descend: Period\n"),
             plain("Get Weapon: G\n"),
             plain("Fire Ranged Weapon: 1,2,or 3\n\n"),
@@ -85,7 +130,30 @@ pub fn help(width: u32) -> AppCF<()> {
     )
 }
 
-pub fn epilogue1(width: u32) -> AppCF<()> {
+pub const fn objective_name(typ: ObjectiveType) -> &'static str {
+    match typ {
+        ObjectiveType::DestroyRelay => "Destroy the power relay",
+        ObjectiveType::RecoverDogTags => "Recover the dog tags",
+        ObjectiveType::ClearServerRoom => "Clear the server room",
+    }
+}
+
+pub fn upgrade_name(upgrade: Upgrade) -> String {
+    let name = match upgrade.typ {
+        UpgradeType::Toughness => "Toughness",
+        UpgradeType::Accuracy => "Accuracy",
+        UpgradeType::Endurance => "Endurance",
+        UpgradeType::Sensors => "Sensors",
+    };
+    let level = match upgrade.level {
+        UpgradeLevel::Level1 => "1",
+        UpgradeLevel::Level2 => "2",
+        UpgradeLevel::Level3 => "3",
+    };
+    format!("{} {}", name, level)
+}
+
+pub fn epilogue_sacrificed(width: u32) -> AppCF<()> {
     text_component(
         width,
         vec![
@@ -98,8 +166,24 @@ pub fn epilogue1(width: u32) -> AppCF<()> {
     )
 }
 
-pub fn epilogue(width: u32) -> AppCF<()> {
-    epilogue1(width)
+pub fn epilogue_survived(width: u32) -> AppCF<()> {
+    text_component(
+        width,
+        vec![
+            plain("The elevator doors grind shut as the station tears itself apart around you. "),
+            plain("Through the viewport you watch the reactor's light swallow the last of the robots.\n\n"),
+            plain("You pickup your radio and radio in \"Mission Acomplished. Requesting pickup.\"\n\n"),
+            plain("For the first time in years, someone answers."),
+            faint("\n\n\n\nPress any key..."),
+        ],
+    )
+}
+
+pub fn epilogue(width: u32, ending: Ending) -> AppCF<()> {
+    match ending {
+        Ending::Survived => epilogue_survived(width),
+        Ending::Sacrificed => epilogue_sacrificed(width),
+    }
 }
 
 pub fn game_over(width: u32) -> AppCF<()> {
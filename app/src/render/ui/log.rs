@@ -1,5 +1,8 @@
 use crate::prelude::*;
-use gridbugs::chargrid::{border::BorderStyle, text::Text};
+use gridbugs::chargrid::{
+    border::BorderStyle,
+    text::{StyledString, Text},
+};
 
 const N: usize = 6;
 
@@ -26,12 +29,93 @@ impl Component for Log {
                 Message::EnemyStunned(npc_type) => {
                     vec![plain("The "), enemy_text(*npc_type), plain(" is stunned.")]
                 }
+                Message::EnemyRepaired(npc_type) => vec![
+                    plain("The "),
+                    enemy_text(NpcType::RepairBot),
+                    plain(" repairs the "),
+                    enemy_text(*npc_type),
+                    plain("."),
+                ],
+                Message::EnemyShielded(npc_type) => vec![
+                    plain("The "),
+                    enemy_text(NpcType::ShieldBot),
+                    plain(" shields the "),
+                    enemy_text(*npc_type),
+                    plain(" from harm!"),
+                ],
                 Message::EnemySlammedIntoWall(npc_type) => {
                     vec![plain("The "), enemy_text(*npc_type), plain(" is slammed into the wall.")]
                 }
+                Message::EnemyFellIntoChasm(npc_type) => {
+                    vec![plain("The "), enemy_text(*npc_type), plain(" falls into the chasm!")]
+                }
                 Message::DoomBotExplodes => {
                     vec![plain("The "), enemy_text(NpcType::DoomBot), plain(" self destructs!")]
                 }
+                Message::CameraSpottedPlayer => {
+                    vec![plain("A security camera spots you and reports your position!")]
+                }
+                Message::AlarmRaised => {
+                    vec![plain("The "), enemy_text(NpcType::RoboCop), plain(" raises the alarm!")]
+                }
+                Message::EnemyHacked(npc_type) => {
+                    vec![plain("You hack the "), enemy_text(*npc_type), plain(". It fights for you now!")]
+                }
+                Message::HackWoreOff(npc_type) => {
+                    vec![plain("The hack on the "), enemy_text(*npc_type), plain(" wears off!")]
+                }
+                Message::AllyHitEnemy { ally, enemy } => vec![
+                    plain("Your hacked "),
+                    enemy_text(*ally),
+                    plain(" hits the "),
+                    enemy_text(*enemy),
+                    plain("!"),
+                ],
+                Message::EnemyHitAlly { enemy, ally } => vec![
+                    plain("The "),
+                    enemy_text(*enemy),
+                    plain(" hits your hacked "),
+                    enemy_text(*ally),
+                    plain("!"),
+                ],
+                Message::ReactorShielded => vec![plain("The reactor's shield absorbs the damage.")],
+                Message::ShieldGeneratorDestroyed => vec![plain("A shield generator goes dark.")],
+                Message::ReactorExposed => vec![bold("The reactor's shield is down!")],
+                Message::ReactorPhase(_) => {
+                    vec![bold("The reactor overloads! "), plain("Vents burst open and DoomBots pour out!")]
+                }
+                Message::ReactorDestroyed => vec![bold("The reactor is destroyed!")],
+                Message::MeltdownStarted => {
+                    vec![bold("Meltdown imminent! "), plain("Get back to the extraction elevator!")]
+                }
+                Message::MeltdownCountdown(turns) => vec![bold(&format!("{} turns until meltdown!", turns))],
+                Message::PlayerBurns => vec![plain("You are burned by the flames!")],
+                Message::WallCollapses => vec![plain("A wall collapses in the heat!")],
+                Message::Evacuated => vec![bold("The elevator doors close behind you.")],
+                Message::CameraDestroyed => vec![plain("The security camera is destroyed.")],
+                Message::AlarmPanelDestroyed => vec![plain("The alarm panel is destroyed.")],
+                Message::PowerRelayDestroyed => {
+                    vec![plain("The power relay shorts out in a shower of sparks.")]
+                }
+                Message::HullBreach => {
+                    vec![bold("The shot punches through the hull! "), plain("Air roars out into space.")]
+                }
+                Message::LowOxygen => vec![bold("Your oxygen is running low!")],
+                Message::Suffocating => vec![bold("You can't breathe!")],
+                Message::GeneratorDestroyed => vec![plain("The generator grinds to a halt.")],
+                Message::Blackout => {
+                    vec![bold("The lights go out! "), plain("Nearby doors jam open and turrets power down.")]
+                }
+                Message::SecretDoorFound => vec![bold("You find a secret door!")],
+                Message::SecretDoorBlownOpen => {
+                    vec![bold("The blast reveals a secret door! "), plain("It hangs open.")]
+                }
+                Message::ObjectiveComplete(typ) => {
+                    vec![bold("Objective complete: "), plain(objective_name(*typ)), plain(".")]
+                }
+                Message::ObjectiveRewardUpgrade(upgrade) => {
+                    vec![plain("You unlock the "), bold(&upgrade_name(*upgrade)), plain(" upgrade for free.")]
+                }
                 Message::AlertedNearbyEntities => {
                     vec![plain("The "), enemy_text(NpcType::RoboCop), plain(" is alerting nearby robots!")]
                 }
@@ -55,6 +139,19 @@ impl Component for Log {
                 Message::Descend => {
                     vec![plain("You descend further into the compound... Your ammo has been refilled!")]
                 }
+                Message::TakeKeycard(clearance) => vec![
+                    plain("You pick up a "),
+                    StyledString {
+                        string: format!("{} keycard", clearance),
+                        style: Style::new().with_foreground(clearance_colour(*clearance)).with_bold(true),
+                    },
+                    plain("."),
+                ],
+                Message::FlashlightFlat => vec![plain("Your flashlight flickers and dies.")],
+                Message::FlashlightRecharged => vec![plain("You recharge your flashlight.")],
+                Message::TakeDogTags => {
+                    vec![plain("You pick up the dog tags. Someone should remember them.")]
+                }
                 Message::TakeCredit(credit) => {
                     vec![plain("You gain "), bold(&format!("{}", credit)), plain(" credits.")]
                 }
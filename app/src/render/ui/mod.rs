@@ -18,7 +18,9 @@ impl Component for Hud {
         let player = state.player();
         let player_info = state.player_info();
 
-        let stunned_txt = if player_info.stunned { "** Stunned **" } else { "" };
+        let stunned_txt = if player_info.stunned { "  ** Stunned **" } else { "" };
+        let flashlight = player.flashlight;
+        let battery = flashlight.battery * 100 / FLASHLIGHT_BATTERY;
         let text = vec![
             plain("Health: "),
             StyledString {
@@ -32,27 +34,77 @@ impl Component for Hud {
                 style: Style::new().with_foreground(color::CREDIT_FOREGROUND).with_bold(true),
             },
             plain("\n"),
+            plain("Keycard: "),
+            match player.clearance {
+                Some(clearance) => StyledString {
+                    string: format!("{} ⌐", clearance),
+                    style: Style::new().with_foreground(clearance_colour(clearance)).with_bold(true),
+                },
+                None => plain("None"),
+            },
+            plain("\n"),
+            plain("Light: "),
+            StyledString {
+                string: format!("{} {}%", if flashlight.on { "ON" } else { "OFF" }, battery),
+                style: Style::new().with_foreground(color::FLASHLIGHT).with_bold(flashlight.on),
+            },
             plain(stunned_txt),
         ];
         Text::from(text).render(&(), ctx, fb);
 
-        let mut y = 4;
+        if player.oxygen < player.max_oxygen() {
+            let text = vec![
+                plain("Oxygen: "),
+                StyledString {
+                    string: format!("{}/{}", player.oxygen, player.max_oxygen()),
+                    style: Style::new().with_foreground(color::OXYGEN).with_bold(true),
+                },
+            ];
+            Text::from(text).render(&(), ctx.add_y(4), fb);
+        } else if let Some(reactor) = state.reactor_info() {
+            render_reactor_bar(&reactor, ctx.add_y(4), fb);
+        } else if let Some(meltdown) = state.meltdown() {
+            let text = vec![
+                plain("Meltdown: "),
+                StyledString {
+                    string: format!("{}", meltdown.turns_remaining),
+                    style: Style::new().with_foreground(color::REACTOR).with_bold(true),
+                },
+            ];
+            Text::from(text).render(&(), ctx.add_y(4), fb);
+        } else if !state.objectives().is_empty() {
+            let objectives = state.objectives();
+            let complete = objectives.iter().filter(|objective| objective.complete).count();
+            let text = vec![
+                plain("Objectives: "),
+                StyledString {
+                    string: format!("{}/{}", complete, objectives.len()),
+                    style: Style::new().with_foreground(color::OBJECTIVE).with_bold(true),
+                },
+            ];
+            Text::from(text).render(&(), ctx.add_y(4), fb);
+        }
+
+        // Weapons are packed closer together to make room for the motion tracker
+        let motion_tracker = state.motion_tracker();
+        let stride = if motion_tracker.is_some() { 6 } else { 7 };
+
+        let mut y = 5;
         render_weapon("Melee:", &player.melee_weapon, player, ctx.add_y(y), fb);
 
-        y += 7;
-        let ctx = ctx.add_y(y);
+        y += stride;
         for (i, ranged_slot) in player.ranged_weapons.iter().enumerate() {
             if let Some(weapon) = ranged_slot {
-                render_weapon(
-                    format!("Ranged {}:", i + 1).as_str(),
-                    weapon,
-                    player,
-                    ctx.add_y(i as i32 * 7),
-                    fb,
-                );
+                render_weapon(format!("Ranged {}:", i + 1).as_str(), weapon, player, ctx.add_y(y), fb);
             } else {
-                render_empty_weapon_slot(format!("Ranged {}:", i + 1).as_str(), ctx.add_y(i as i32 * 7), fb);
+                render_empty_weapon_slot(format!("Ranged {}:", i + 1).as_str(), ctx.add_y(y), fb);
             }
+            y += stride;
+        }
+
+        if let Some(motion_tracker) = motion_tracker {
+            let rows = GAME_VIEW_SIZE.height() as i32 - y;
+            render_motion_tracker(&motion_tracker, rows, ctx.add_y(y), fb);
         }
     }
 
@@ -63,6 +115,72 @@ impl Component for Hud {
     }
 }
 
+const REACTOR_BAR_WIDTH: u32 = 10;
+
+fn render_reactor_bar(reactor: &ReactorInfo, ctx: Ctx, fb: &mut FrameBuffer) {
+    let HitPoints { current, max } = reactor.hit_points;
+    let filled = (current * REACTOR_BAR_WIDTH + max - 1) / max;
+    let bar_colour = if reactor.shielded { color::SHIELD_GENERATOR } else { color::REACTOR };
+    let shields = if reactor.shielded { format!(" Ω{}", reactor.shield_generators) } else { String::new() };
+
+    let text = vec![
+        plain("Reactor: "),
+        StyledString {
+            string: "█".repeat(filled as usize),
+            style: Style::new().with_foreground(bar_colour).with_bold(true),
+        },
+        StyledString {
+            string: "░".repeat((REACTOR_BAR_WIDTH - filled) as usize),
+            style: Style::new().with_foreground(Rgba32::new_grey(100)),
+        },
+        StyledString {
+            string: shields,
+            style: Style::new().with_foreground(color::SHIELD_GENERATOR).with_bold(true),
+        },
+    ];
+    Text::from(text).render(&(), ctx, fb);
+}
+
+const RADAR_HALF_WIDTH: i32 = 7;
+const RADAR_MAX_HALF_HEIGHT: i32 = 2;
+
+/// Draws the pings as blips on a small grid centred on the player. The grid is squashed to fit
+/// however many rows are left below the weapons, so blips show direction and rough distance.
+fn render_motion_tracker(motion_tracker: &MotionTracker, rows: i32, ctx: Ctx, fb: &mut FrameBuffer) {
+    let half_height = ((rows - 2) / 2).clamp(0, RADAR_MAX_HALF_HEIGHT);
+    let nearest = motion_tracker.pings.iter().map(|ping| ping.distance()).min();
+    let text = vec![
+        plain("Tracker: "),
+        StyledString {
+            string: nearest.map_or_else(|| "--".to_string(), |distance| format!("{}m", distance)),
+            style: Style::new().with_foreground(color::MOTION_BLIP).with_bold(true),
+        },
+    ];
+    Text::from(text).render(&(), ctx, fb);
+
+    let range = motion_tracker.range.max(1) as i32;
+    let centre = Coord::new(RADAR_HALF_WIDTH, 1 + half_height);
+    for y in -half_height..=half_height {
+        for x in -RADAR_HALF_WIDTH..=RADAR_HALF_WIDTH {
+            let cell = RenderCell::BLANK.with_character('·').with_foreground(color::MOTION_TRACKER);
+            fb.set_cell_relative_to_ctx(ctx, centre + Coord::new(x, y), 0, cell);
+        }
+    }
+    let player_cell = RenderCell::BLANK.with_character('@').with_foreground(color::PLAYER).with_bold(true);
+    fb.set_cell_relative_to_ctx(ctx, centre, 1, player_cell);
+    for ping in &motion_tracker.pings {
+        let offset = Coord::new(
+            (ping.offset.x * RADAR_HALF_WIDTH + ping.offset.x.signum() * range / 2) / range,
+            (ping.offset.y * half_height + ping.offset.y.signum() * range / 2) / range,
+        );
+        if offset == Coord::new(0, 0) {
+            continue;
+        }
+        let blip = RenderCell::BLANK.with_character('•').with_foreground(color::MOTION_BLIP).with_bold(true);
+        fb.set_cell_relative_to_ctx(ctx, centre + offset, 1, blip);
+    }
+}
+
 pub fn render_hud(scope: &StateScope, ctx: Ctx, fb: &mut FrameBuffer) {
     cf(Hud {})
         .border(BorderStyle { foreground: Rgba32::new_grey(128), ..Default::default() })
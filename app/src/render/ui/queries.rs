@@ -32,6 +32,11 @@ pub fn enemy_text(enemy: NpcType) -> StyledString {
         NpcType::SecBot => t("Secbot", color::SECBOT),
         NpcType::RoboCop => t("RoboCop", color::ROBOCOP.saturating_scalar_mul_div(3, 2)),
         NpcType::DoomBot => t("DoomBot", color::DOOMBOT.saturating_scalar_mul_div(3, 2)),
+        NpcType::Turret => t("Turret", color::TURRET),
+        NpcType::Drone => t("Drone", color::DRONE),
+        NpcType::Skimmer => t("Skimmer", color::SKIMMER),
+        NpcType::RepairBot => t("RepairBot", color::REPAIRBOT),
+        NpcType::ShieldBot => t("ShieldBot", color::SHIELDBOT),
     }
 }
 
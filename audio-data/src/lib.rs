@@ -11,6 +11,7 @@ lazy_static! {
     pub static ref GAMEPLAY2: &'static [u8] = OVERLAY_MUSIC.get_file("level_3.ogg").unwrap().contents();
     pub static ref GAMEPLAY3: &'static [u8] = OVERLAY_MUSIC.get_file("level_4.ogg").unwrap().contents();
     pub static ref GAMEPLAY4: &'static [u8] = OVERLAY_MUSIC.get_file("level_5.ogg").unwrap().contents();
+    pub static ref REACTOR: &'static [u8] = OVERLAY_MUSIC.get_file("Orbital Decay.ogg").unwrap().contents();
     pub static ref MENU: &'static [u8] = OVERLAY_MUSIC.get_file("menu.ogg").unwrap().contents();
     pub static ref END_TEXT_SAD: &'static [u8] = OVERLAY_MUSIC.get_file("sad_ending.ogg").unwrap().contents();
     pub static ref END_TEXT_HAPPY: &'static [u8] =
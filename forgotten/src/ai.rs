@@ -9,8 +9,65 @@ impl Game {
         self.update_behaviour();
     }
 
+    pub fn security_turn(&mut self) {
+        let player_coord = self.player_coord();
+        let sightings = self
+            .world
+            .components
+            .camera
+            .iter()
+            .filter_map(|(entity, camera)| {
+                let camera_coord = self.world.entity_coord(entity)?;
+                let can_see = camera_can_see(&self.world, camera_coord, camera.facing, player_coord);
+                Some((entity, can_see.then_some(player_coord)))
+            })
+            .collect::<Vec<_>>();
+
+        for (camera_entity, sighting) in sightings {
+            self.world.update_camera(camera_entity, sighting);
+        }
+    }
+
+    /// Sends in reinforcements whenever the reactor enters a new phase
+    pub fn reactor_turn(&mut self) {
+        if let Some(coords) = self.world.update_reactor() {
+            for coord in coords {
+                // Reinforcements can't arrive somewhere already taken
+                if let Ok(doom) = self.world.spawn_doombot(coord) {
+                    self.agents.insert(doom, Agent::new(self.world.size(), NpcType::DoomBot));
+                }
+            }
+        }
+    }
+
+    /// Counts down the meltdown while the station burns and falls apart around the player
+    pub fn meltdown_turn(&mut self) {
+        let meltdown = if let Some(meltdown) = self.meltdown.as_mut() { meltdown } else { return };
+        meltdown.turns_remaining = meltdown.turns_remaining.saturating_sub(1);
+
+        let turns_remaining = meltdown.turns_remaining;
+        if turns_remaining % 10 == 0 || turns_remaining <= 5 {
+            crate::log::append_entry(Message::MeltdownCountdown(turns_remaining));
+        }
+
+        self.world.spread_fire();
+        self.world.collapse_wall();
+        self.world.burn_characters();
+    }
+
     pub fn npc_turn(&mut self) {
         self.update_behaviour();
+        self.security_turn();
+        self.reactor_turn();
+        self.meltdown_turn();
+
+        // Remember where everyone started so the motion tracker can tell who moved this turn
+        self.world.components.moved.clear();
+        let start_coords = self
+            .agents
+            .entities()
+            .filter_map(|entity| self.world.entity_coord(entity).map(|coord| (entity, coord)))
+            .collect::<Vec<_>>();
 
         for (entity, agent) in self.agents.iter_mut() {
             if !self.world.entity_exists(entity) {
@@ -18,14 +75,40 @@ impl Game {
                 continue;
             }
 
+            // Forget about an alert once the robot reaches the reported location
+            let coord = self.world.entity_coord(entity);
+            if let Some(npc) = self.world.components.npc.get_mut(entity) {
+                if npc.move_to.is_some() && npc.move_to == coord {
+                    npc.move_to = None;
+                }
+            }
+
+            self.world.reduce_hack(entity);
+
+            // Turrets in a blacked out wing have no power to track or fire
+            if !self.world.is_powered(entity) {
+                continue;
+            }
+
+            // Turrets never walk, so their stun has to wear off here instead
+            if self.world.is_stationary(entity) && self.world.check_movement_blocked(entity) {
+                self.world.reduce_stun(entity);
+                continue;
+            }
+
             match agent.act(entity, &self.world, self.player_entity, &mut self.behavior_context) {
                 NpcAction::Wait => (),
+                NpcAction::RaiseAlarm => self.world.raise_alarm(entity, self.player_entity),
                 NpcAction::Alert(direction) => {
                     self.world.alert_nearby_entities(entity, direction, self.player_entity)
                 }
                 NpcAction::Walk(direction) => {
                     let _ = self.world.character_walk_in_direction(entity, direction);
                 }
+                NpcAction::Repair(target) => self.world.repair_character(entity, target),
+                NpcAction::FireAt(target) => {
+                    self.world.character_fire_bullet(entity, target, RangedWeaponSlot::Slot1);
+                }
                 NpcAction::FireBullet(direction) => {
                     let agent_coord = self.world.entity_coord(entity).unwrap();
 
@@ -37,5 +120,11 @@ impl Game {
                 }
             }
         }
+
+        for (entity, start_coord) in start_coords {
+            if self.world.entity_coord(entity).map_or(false, |coord| coord != start_coord) {
+                self.world.components.moved.insert(entity, ());
+            }
+        }
     }
 }
@@ -16,21 +16,24 @@ use gridbugs::{
 
 struct WorldCanEnterIgnoreCharacters<'a> {
     world: &'a World,
+    mobility: Mobility,
 }
 
 impl<'a> CanEnter for WorldCanEnterIgnoreCharacters<'a> {
     fn can_enter(&self, coord: Coord) -> bool {
-        self.world.can_npc_traverse_feature_at_coord(coord)
+        self.world.can_npc_traverse_feature_at_coord(coord, self.mobility)
     }
 }
 
 struct WorldCanEnterAvoidNpcs<'a> {
     world: &'a World,
+    mobility: Mobility,
 }
 
 impl<'a> CanEnter for WorldCanEnterAvoidNpcs<'a> {
     fn can_enter(&self, coord: Coord) -> bool {
-        self.world.can_npc_traverse_feature_at_coord(coord) && !self.world.is_npc_at_coord(coord)
+        self.world.can_npc_traverse_feature_at_coord(coord, self.mobility)
+            && !self.world.is_npc_at_coord(coord)
     }
 
     fn can_step(&self, step: Step) -> bool {
@@ -39,6 +42,78 @@ impl<'a> CanEnter for WorldCanEnterAvoidNpcs<'a> {
 }
 
 const MAX_DISTANCE: Distance = 5;
+const CAMERA_VISION_DISTANCE_SQUARED: u32 = 100;
+const ALARM_PANEL_DISTANCE_SQUARED: u32 = 400;
+const TURRET_VISION_DISTANCE_SQUARED: u32 = 80;
+const REPAIR_DISTANCE_SQUARED: u32 = 9;
+const REPAIR_COOLDOWN: u8 = 3;
+const ESCORT_DISTANCE_SQUARED: u32 = 64;
+const AMPHIBIOUS_LAND_PENALTY: u64 = 10;
+const ALLY_FOLLOW_DISTANCE_SQUARED: u32 = 8;
+
+// Returns the first step towards the traversable cell next to `target` which is closest to `coord`
+fn walk_next_to(
+    world: &World,
+    mobility: Mobility,
+    behaviour_context: &mut BehaviourContext,
+    coord: Coord,
+    target: Coord,
+) -> Option<CardinalDirection> {
+    let access_coord = CardinalDirection::all()
+        .map(|direction| target + direction.coord())
+        .filter(|&c| world.can_npc_traverse_feature_at_coord(c, mobility))
+        .min_by_key(|c| c.distance2(coord))?;
+
+    behaviour_context
+        .point_to_point_search_context
+        .point_to_point_search_first(
+            expand::JumpPoint,
+            &WorldCanEnterAvoidNpcs { world, mobility },
+            coord,
+            access_coord,
+        )
+        .ok()
+        .flatten()
+}
+
+// Returns the coord of the closest hacked robot that a hostile `entity` can see, as long as it's
+// nearer than the player or the player is out of sight
+fn nearest_visible_ally(
+    entity: Entity,
+    coord: Coord,
+    world: &World,
+    player: Entity,
+    vision_distance: vision_distance::Circle,
+) -> Option<Coord> {
+    let ally_coord = nearest_visible_enemy(entity, coord, world, vision_distance)?;
+    let player_nearer = world
+        .entity_coord(player)
+        .filter(|&player_coord| player_coord.distance2(coord) <= ally_coord.distance2(coord))
+        .filter(|&player_coord| has_line_of_sight(coord, player_coord, world, vision_distance))
+        .is_some();
+    if player_nearer {
+        None
+    } else {
+        Some(ally_coord)
+    }
+}
+
+// Returns the coord of the closest robot on the other side of a hack that `entity` can see
+fn nearest_visible_enemy(
+    entity: Entity,
+    coord: Coord,
+    world: &World,
+    vision_distance: vision_distance::Circle,
+) -> Option<Coord> {
+    world
+        .components
+        .npc
+        .entities()
+        .filter(|&other| world.entity_exists(other) && world.are_robots_hostile(entity, other))
+        .filter_map(|other| world.entity_coord(other))
+        .filter(|&other_coord| has_line_of_sight(coord, other_coord, world, vision_distance))
+        .min_by_key(|other_coord| other_coord.distance2(coord))
+}
 
 fn has_line_of_sight(
     eye: Coord,
@@ -58,14 +133,35 @@ fn has_line_of_sight(
     true
 }
 
+/// Returns true iff a camera mounted at `camera_coord` can see `target`. Cameras see in a 90 degree
+/// cone in the direction they are facing, starting from the cell in front of the camera.
+pub fn camera_can_see(world: &World, camera_coord: Coord, facing: CardinalDirection, target: Coord) -> bool {
+    let delta = target - camera_coord;
+    let facing_coord = facing.coord();
+    let forward = delta.x * facing_coord.x + delta.y * facing_coord.y;
+    let lateral = (delta.x * facing_coord.y - delta.y * facing_coord.x).abs();
+    if forward <= 0 || lateral > forward || delta.magnitude2() > CAMERA_VISION_DISTANCE_SQUARED {
+        return false;
+    }
+
+    has_line_of_sight(
+        camera_coord + facing_coord,
+        target,
+        world,
+        vision_distance::Circle::new_squared(CAMERA_VISION_DISTANCE_SQUARED),
+    )
+}
+
 #[derive(Serialize, Deserialize)]
 pub struct BehaviourContext {
     best_search_context: BestSearchContext,
     point_to_point_search_context: PointToPointSearchContext,
     distance_map_populate_context: DistanceMapPopulateContext,
     distance_map_search_context: DistanceMapSearchContext,
-    player_approach: DistanceMap,
-    player_flee: DistanceMap,
+    // One map of each kind for every combination of movement profile and clearance, indexed by
+    // `Mobility::index`
+    player_approach: Vec<DistanceMap>,
+    player_flee: Vec<DistanceMap>,
     wander_path: Path,
 }
 
@@ -76,24 +172,39 @@ impl BehaviourContext {
             point_to_point_search_context: PointToPointSearchContext::new(size),
             distance_map_populate_context: DistanceMapPopulateContext::default(),
             distance_map_search_context: DistanceMapSearchContext::new(size),
-            player_approach: DistanceMap::new(size),
-            player_flee: DistanceMap::new(size),
+            player_approach: (0..Mobility::COUNT).map(|_| DistanceMap::new(size)).collect(),
+            player_flee: (0..Mobility::COUNT).map(|_| DistanceMap::new(size)).collect(),
             wander_path: Path::default(),
         }
     }
 
     pub fn update(&mut self, player: Entity, world: &World) {
         if let Some(player_coord) = world.entity_coord(player) {
-            let can_enter = WorldCanEnterIgnoreCharacters { world };
-            self.distance_map_populate_context.add(player_coord);
-            self.distance_map_populate_context.populate_approach(&can_enter, 20, &mut self.player_approach);
-            self.distance_map_populate_context.add(player_coord);
-            self.distance_map_populate_context.populate_flee(&can_enter, 20, &mut self.player_flee);
+            for mobility in Mobility::all() {
+                let can_enter = WorldCanEnterIgnoreCharacters { world, mobility };
+                let index = mobility.index();
+                self.distance_map_populate_context.add(player_coord);
+                self.distance_map_populate_context.populate_approach(
+                    &can_enter,
+                    20,
+                    &mut self.player_approach[index],
+                );
+                self.distance_map_populate_context.add(player_coord);
+                self.distance_map_populate_context.populate_flee(
+                    &can_enter,
+                    20,
+                    &mut self.player_flee[index],
+                );
+            }
         } else {
-            self.player_approach.clear();
-            self.player_flee.clear();
+            self.player_approach.iter_mut().for_each(DistanceMap::clear);
+            self.player_flee.iter_mut().for_each(DistanceMap::clear);
         }
     }
+
+    fn player_approach(&self, mobility: Mobility) -> &DistanceMap {
+        &self.player_approach[mobility.index()]
+    }
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -101,6 +212,7 @@ enum Behaviour {
     Flee,
     Wander { avoid: bool },
     Chase { last_seen_player_coord: Coord, accurate: bool },
+    RaiseAlarm { panel: Coord, last_seen_player_coord: Coord },
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
@@ -109,12 +221,26 @@ pub enum NpcAction {
     Walk(CardinalDirection),
     Alert(CardinalDirection),
     FireBullet(CardinalDirection),
+    FireAt(Coord),
+    Repair(Entity),
+    RaiseAlarm,
+}
+
+// Amphibious robots treat dry land as if they had visited it recently, so they drift towards water
+// when wandering
+fn wander_count(world: &World, profile: MovementProfile, coord: Coord, count: u64) -> u64 {
+    if profile == MovementProfile::Amphibious && !world.is_water_at_coord(coord) {
+        count + AMPHIBIOUS_LAND_PENALTY
+    } else {
+        count
+    }
 }
 
 struct Wander<'a> {
     avoid: bool,
     entity: Entity,
     world: &'a World,
+    mobility: Mobility,
     min_last_seen_count: u64,
     min_last_seen_coord: Option<Coord>,
     last_seen_grid: &'a VisibilityGrid<LastSeenCell>,
@@ -126,7 +252,7 @@ impl<'a> BestSearch for Wander<'a> {
     }
 
     fn can_enter_initial_updating_best(&mut self, coord: Coord) -> bool {
-        if self.world.can_npc_traverse_feature_at_coord(coord) {
+        if self.world.can_npc_traverse_feature_at_coord(coord, self.mobility) {
             if let Some(entity) = self.world.get_character_at_coord(coord) {
                 if entity != self.entity {
                     let my_coord = self.world.entity_coord(self.entity).unwrap();
@@ -150,7 +276,8 @@ impl<'a> BestSearch for Wander<'a> {
                     return false;
                 }
 
-                let last_seen_count = last_seen_cell.count;
+                let last_seen_count =
+                    wander_count(self.world, self.mobility.profile, coord, last_seen_cell.count);
                 if last_seen_count <= self.min_last_seen_count {
                     self.min_last_seen_count = last_seen_count;
                     self.min_last_seen_coord = Some(coord);
@@ -176,6 +303,8 @@ impl<'a> BestSearch for Wander<'a> {
 pub struct Agent {
     npc_type: NpcType,
     behaviour: Behaviour,
+    raised_alarm: bool,
+    repair_cooldown: u8,
     vision_distance: vision_distance::Circle,
     last_seen_grid: VisibilityGrid<LastSeenCell>,
 }
@@ -187,9 +316,8 @@ struct LastSeenCell {
 }
 
 impl LastSeenCell {
-    pub fn update(&mut self, coord: Coord, can_see_player: bool, behaviour_context: &BehaviourContext) {
+    pub fn update(&mut self, coord: Coord, can_see_player: bool, distance_map_to_player: &DistanceMap) {
         self.count += 1;
-        let distance_map_to_player = &behaviour_context.player_approach;
 
         if can_see_player {
             if let Some(distance_to_player) = distance_map_to_player.distance(coord) {
@@ -205,12 +333,196 @@ impl Agent {
     pub fn new(size: Size, npc_type: NpcType) -> Self {
         Self {
             npc_type,
+            raised_alarm: false,
+            repair_cooldown: 0,
             last_seen_grid: VisibilityGrid::new(size),
             vision_distance: vision_distance::Circle::new_squared(40),
             behaviour: Behaviour::Wander { avoid: true },
         }
     }
 
+    /// RoboCops that see the player run to the nearest alarm panel before raising the alarm
+    fn nearest_alarm_panel(&self, world: &World, coord: Coord) -> Option<Coord> {
+        if self.npc_type != NpcType::RoboCop || self.raised_alarm {
+            return None;
+        }
+
+        world
+            .components
+            .alarm_panel
+            .entities()
+            .filter_map(|panel| world.entity_coord(panel))
+            .filter(|panel_coord| panel_coord.distance2(coord) <= ALARM_PANEL_DISTANCE_SQUARED)
+            .min_by_key(|panel_coord| panel_coord.distance2(coord))
+    }
+
+    /// Turrets can't move, so they just track their target and fire whenever they have a clear shot
+    fn turret_act(&self, entity: Entity, coord: Coord, world: &World, player: Entity) -> NpcAction {
+        if !world.entity_has_ammo(entity, RangedWeaponSlot::Slot1) {
+            return NpcAction::Wait;
+        }
+
+        let vision_distance = vision_distance::Circle::new_squared(TURRET_VISION_DISTANCE_SQUARED);
+        let target = if world.is_allied(entity) {
+            nearest_visible_enemy(entity, coord, world, vision_distance)
+        } else {
+            nearest_visible_ally(entity, coord, world, player, vision_distance).or_else(|| {
+                world
+                    .entity_coord(player)
+                    .filter(|&player_coord| has_line_of_sight(coord, player_coord, world, vision_distance))
+            })
+        };
+
+        target.map_or(NpcAction::Wait, NpcAction::FireAt)
+    }
+
+    /// Hacked robots go after the nearest hostile robot they can see, and otherwise stay close to
+    /// the player
+    fn allied_act(
+        &self,
+        entity: Entity,
+        coord: Coord,
+        world: &World,
+        player: Entity,
+        behaviour_context: &mut BehaviourContext,
+    ) -> NpcAction {
+        let mobility = self.npc_type.mobility();
+
+        if let Some(enemy_coord) = nearest_visible_enemy(entity, coord, world, self.vision_distance) {
+            if let Some(action) = self.fight(entity, coord, world, enemy_coord, behaviour_context) {
+                return action;
+            }
+        }
+
+        match world.entity_coord(player) {
+            Some(player_coord) if player_coord.distance2(coord) > ALLY_FOLLOW_DISTANCE_SQUARED => {
+                walk_next_to(world, mobility, behaviour_context, coord, player_coord)
+                    .map_or(NpcAction::Wait, NpcAction::Walk)
+            }
+            _ => NpcAction::Wait,
+        }
+    }
+
+    /// Closes in on a robot on the other side of a hack, attacking it once it's in reach or shooting
+    /// at it if there's ammo to spare
+    fn fight(
+        &self,
+        entity: Entity,
+        coord: Coord,
+        world: &World,
+        enemy_coord: Coord,
+        behaviour_context: &mut BehaviourContext,
+    ) -> Option<NpcAction> {
+        if let Some(direction) = CardinalDirection::all().find(|d| coord + d.coord() == enemy_coord) {
+            return Some(NpcAction::Walk(direction));
+        }
+
+        if world.entity_has_ammo(entity, RangedWeaponSlot::Slot1) {
+            return Some(NpcAction::FireAt(enemy_coord));
+        }
+
+        walk_next_to(world, self.npc_type.mobility(), behaviour_context, coord, enemy_coord)
+            .map(NpcAction::Walk)
+    }
+
+    /// Finds the most damaged robot that the repair bot can see within `distance_squared`
+    fn most_damaged_ally(
+        &self,
+        entity: Entity,
+        coord: Coord,
+        world: &World,
+        distance_squared: u32,
+    ) -> Option<(Entity, Coord)> {
+        world
+            .components
+            .npc
+            .entities()
+            .filter(|&ally| {
+                ally != entity
+                    && world.entity_exists(ally)
+                    && world.is_allied(ally) == world.is_allied(entity)
+            })
+            .filter_map(|ally| {
+                let hit_points = world.components.hp.get(ally)?;
+                let ally_coord = world.entity_coord(ally)?;
+                (hit_points.current < hit_points.max).then_some((
+                    ally,
+                    ally_coord,
+                    hit_points.max - hit_points.current,
+                ))
+            })
+            .filter(|&(_, ally_coord, _)| {
+                ally_coord.distance2(coord) <= distance_squared
+                    && has_line_of_sight(coord, ally_coord, world, self.vision_distance)
+            })
+            .max_by_key(|&(_, _, missing)| missing)
+            .map(|(ally, ally_coord, _)| (ally, ally_coord))
+    }
+
+    /// Repair bots patch up any damaged robot within range, and otherwise move towards damaged
+    /// robots that they can see as long as they aren't busy running from the player
+    fn repair_act(
+        &mut self,
+        entity: Entity,
+        coord: Coord,
+        world: &World,
+        behaviour_context: &mut BehaviourContext,
+    ) -> Option<NpcAction> {
+        self.repair_cooldown = self.repair_cooldown.saturating_sub(1);
+
+        if self.repair_cooldown == 0 {
+            if let Some((ally, _)) = self.most_damaged_ally(entity, coord, world, REPAIR_DISTANCE_SQUARED) {
+                self.repair_cooldown = REPAIR_COOLDOWN;
+                return Some(NpcAction::Repair(ally));
+            }
+        }
+
+        if matches!(self.behaviour, Behaviour::Flee) {
+            return None;
+        }
+
+        let (_, ally_coord) =
+            self.most_damaged_ally(entity, coord, world, self.vision_distance.distance_squared())?;
+        if ally_coord.distance2(coord) <= REPAIR_DISTANCE_SQUARED {
+            return Some(NpcAction::Wait);
+        }
+
+        walk_next_to(world, self.npc_type.mobility(), behaviour_context, coord, ally_coord)
+            .map(NpcAction::Walk)
+    }
+
+    /// Shield bots stick to whichever robot is closest to the player, so that it benefits from the
+    /// shield while it fights
+    fn escort_act(
+        &self,
+        entity: Entity,
+        coord: Coord,
+        world: &World,
+        player_coord: Coord,
+        behaviour_context: &mut BehaviourContext,
+    ) -> Option<NpcAction> {
+        let escorted_coord = world
+            .components
+            .npc
+            .iter()
+            .filter(|&(ally, npc)| {
+                ally != entity
+                    && npc.npc_type != NpcType::ShieldBot
+                    && world.entity_exists(ally)
+                    && world.is_allied(ally) == world.is_allied(entity)
+            })
+            .filter_map(|(ally, _)| world.entity_coord(ally))
+            .filter(|ally_coord| ally_coord.distance2(coord) <= ESCORT_DISTANCE_SQUARED)
+            .min_by_key(|ally_coord| ally_coord.distance2(player_coord))?;
+
+        if (escorted_coord - coord).magnitude2() <= 2 {
+            return Some(NpcAction::Wait);
+        }
+
+        walk_next_to(world, self.npc_type.mobility(), behaviour_context, coord, escorted_coord)
+            .map(NpcAction::Walk)
+    }
+
     pub fn check_action(
         &self,
         entity: Entity,
@@ -251,21 +563,47 @@ impl Agent {
             return NpcAction::Wait;
         };
 
+        if self.npc_type == NpcType::Turret {
+            return self.turret_act(entity, coord, world, player);
+        }
+
         let npc = world.entity_npc(entity);
+        let mobility = self.npc_type.mobility();
+
+        if npc.disposition == Disposition::Allied {
+            return self.allied_act(entity, coord, world, player, behaviour_context);
+        }
 
         self.behaviour = if let Some(player_coord) = world.entity_coord(player) {
-            let can_see_player = has_line_of_sight(coord, player_coord, world, self.vision_distance);
+            // A lit flashlight gives the player away from much further off
+            let spotting_distance = if world.is_flashlight_on(player) {
+                vision_distance::Circle::new_squared(FLASHLIGHT_SPOTTED_DISTANCE_SQUARED)
+            } else {
+                self.vision_distance
+            };
+            let can_see_player = has_line_of_sight(coord, player_coord, world, spotting_distance);
             self.last_seen_grid.update_custom(AMBIENT_COL, world, self.vision_distance, coord, |d, c| {
-                d.update(c, can_see_player, behaviour_context)
+                d.update(c, can_see_player, behaviour_context.player_approach(mobility))
             });
 
             if can_see_player {
                 match npc.disposition {
-                    Disposition::Hostile => {
-                        Behaviour::Chase { last_seen_player_coord: player_coord, accurate: true }
-                    }
+                    Disposition::Hostile => match self.behaviour {
+                        Behaviour::RaiseAlarm { panel, .. } => {
+                            Behaviour::RaiseAlarm { panel, last_seen_player_coord: player_coord }
+                        }
+                        _ => match self.nearest_alarm_panel(world, coord) {
+                            Some(panel) => {
+                                Behaviour::RaiseAlarm { panel, last_seen_player_coord: player_coord }
+                            }
+                            None => Behaviour::Chase { last_seen_player_coord: player_coord, accurate: true },
+                        },
+                    },
+                    // Allies don't go after the player, they just keep out of the way
+                    Disposition::Allied => Behaviour::Wander { avoid: false },
                     Disposition::Afraid => {
-                        if behaviour_context.player_approach.distance(coord).unwrap() < MAX_DISTANCE {
+                        let distance_to_player = behaviour_context.player_approach(mobility).distance(coord);
+                        if distance_to_player.map_or(false, |distance| distance < MAX_DISTANCE) {
                             Behaviour::Flee
                         } else {
                             Behaviour::Wander { avoid: true }
@@ -289,6 +627,9 @@ impl Agent {
                         }
                     }
                     Behaviour::Wander { avoid } => Behaviour::Wander { avoid },
+                    Behaviour::RaiseAlarm { panel, last_seen_player_coord } => {
+                        Behaviour::RaiseAlarm { panel, last_seen_player_coord }
+                    }
                     Behaviour::Flee => {
                         // stop fleeing the player if you can't see them
                         Behaviour::Wander { avoid: true }
@@ -299,8 +640,61 @@ impl Agent {
             Behaviour::Wander { avoid: false }
         };
 
+        // Hacked robots are fought just like the player, whenever one is the closer target
+        if npc.disposition == Disposition::Hostile {
+            if let Some(ally_coord) = nearest_visible_ally(entity, coord, world, player, self.vision_distance)
+            {
+                if let Some(action) = self.fight(entity, coord, world, ally_coord, behaviour_context) {
+                    return action;
+                }
+            }
+        }
+
+        if let Behaviour::RaiseAlarm { panel, last_seen_player_coord } = self.behaviour {
+            if !world.components.alarm_panel.entities().any(|e| world.entity_coord(e) == Some(panel)) {
+                // The panel has been destroyed, so go after the player instead
+                self.behaviour = Behaviour::Chase { last_seen_player_coord, accurate: false };
+            } else if panel.manhattan_distance(coord) == 1 {
+                self.raised_alarm = true;
+                self.behaviour = Behaviour::Chase { last_seen_player_coord, accurate: false };
+                return NpcAction::RaiseAlarm;
+            } else {
+                match walk_next_to(world, mobility, behaviour_context, coord, panel) {
+                    Some(cardinal_direction) => return NpcAction::Walk(cardinal_direction),
+                    None => self.behaviour = Behaviour::Chase { last_seen_player_coord, accurate: false },
+                }
+            }
+        }
+
+        match self.npc_type {
+            NpcType::RepairBot => {
+                if let Some(action) = self.repair_act(entity, coord, world, behaviour_context) {
+                    return action;
+                }
+            }
+            NpcType::ShieldBot => {
+                if let Behaviour::Chase { last_seen_player_coord, accurate: true } = self.behaviour {
+                    if let Some(action) =
+                        self.escort_act(entity, coord, world, last_seen_player_coord, behaviour_context)
+                    {
+                        return action;
+                    }
+                }
+            }
+            _ => {}
+        }
+
         match self.behaviour {
-            Behaviour::Flee => NpcAction::Wait,
+            Behaviour::RaiseAlarm { .. } => NpcAction::Wait,
+            Behaviour::Flee => behaviour_context
+                .distance_map_search_context
+                .search_first(
+                    &WorldCanEnterAvoidNpcs { world, mobility },
+                    coord,
+                    MAX_DISTANCE,
+                    &behaviour_context.player_flee[mobility.index()],
+                )
+                .map_or(NpcAction::Wait, NpcAction::Walk),
             Behaviour::Wander { avoid } => {
                 let mut path_node = behaviour_context.wander_path.pop();
                 let need_new_path = path_node.map_or(true, |path_node| {
@@ -314,9 +708,15 @@ impl Agent {
                             avoid,
                             world,
                             entity,
+                            mobility,
                             min_last_seen_coord: None,
                             last_seen_grid: &self.last_seen_grid,
-                            min_last_seen_count: self.last_seen_grid.get_data(coord).unwrap().count,
+                            min_last_seen_count: wander_count(
+                                world,
+                                mobility.profile,
+                                coord,
+                                self.last_seen_grid.get_data(coord).unwrap().count,
+                            ),
                         },
                         coord,
                         &mut behaviour_context.wander_path,
@@ -331,10 +731,10 @@ impl Agent {
                 if accurate {
                     let maybe_cardinal_direction =
                         behaviour_context.distance_map_search_context.search_first(
-                            &WorldCanEnterAvoidNpcs { world },
+                            &WorldCanEnterAvoidNpcs { world, mobility },
                             coord,
                             MAX_DISTANCE,
-                            &behaviour_context.player_approach,
+                            &behaviour_context.player_approach[mobility.index()],
                         );
 
                     match maybe_cardinal_direction {
@@ -353,7 +753,7 @@ impl Agent {
                 } else {
                     let result = behaviour_context.point_to_point_search_context.point_to_point_search_first(
                         expand::JumpPoint,
-                        &WorldCanEnterAvoidNpcs { world },
+                        &WorldCanEnterAvoidNpcs { world, mobility },
                         coord,
                         last_seen_player_coord,
                     );
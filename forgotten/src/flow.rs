@@ -1,14 +1,28 @@
 use crate::{prelude::*, prompt, terrain};
 use gridbugs::visible_area_detection::VisibilityGrid;
 
+/// How the player left the station once the reactor was destroyed
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Ending {
+    /// Reached the extraction elevator before the meltdown
+    Survived,
+    /// Went down with the station
+    #[default]
+    Sacrificed,
+}
+
 pub enum ControlFlow {
-    Win,
+    Win(Ending),
     Upgrade,
     GetMelee,
     GameOver,
     GetRanged,
+    Interact,
     LevelChange,
+    /// The next floor couldn't be generated, so the player is still on the stairs
+    GenerationFailed(GenerationError),
     Prompt(String),
+    ReadLore(LoreId),
     FireWeapon(RangedWeaponSlot),
 }
 
@@ -46,33 +60,71 @@ impl Game {
             }
         }
 
-        if let Some(countdown) = self.win_countdown {
+        if let Some((countdown, ending)) = self.win_countdown {
             if countdown.as_millis() == 0 {
-                return Some(ControlFlow::Win);
+                return Some(ControlFlow::Win(ending));
             } else {
-                self.win_countdown = Some(
+                self.win_countdown = Some((
                     countdown
                         .checked_sub(since_previous)
                         .map_or_else(|| Duration::from_millis(0), |remaining| remaining),
-                )
+                    ending,
+                ))
             }
         }
 
         self.run_systems();
 
-        if self.is_won() {
-            self.win_countdown = Some(Duration::from_secs(2));
+        if self.win_countdown.is_some() {
+            return None;
+        }
+
+        if self.is_won() && self.meltdown.is_none() {
+            self.start_meltdown();
+        }
+
+        if let Some(ending) = self.meltdown_ending() {
+            if ending == Ending::Survived {
+                crate::log::append_entry(Message::Evacuated);
+            }
+            self.win_countdown = Some((Duration::from_secs(2), ending));
             None
-        } else if self.is_game_over() && self.win_countdown.is_none() {
+        } else if self.is_game_over() {
             Some(ControlFlow::GameOver)
         } else {
             None
         }
     }
 
-    pub fn generate_level(&mut self) {
-        crate::log::append_entry(Message::Descend);
+    // Floors without an extraction elevator end as soon as the reactor goes up
+    fn start_meltdown(&mut self) {
+        let reactor_coord = self.world.components.reactor.entities().find_map(|e| self.world.entity_coord(e));
+        match (self.world.elevator_coord(), reactor_coord) {
+            (Some(_), Some(reactor_coord)) => {
+                crate::log::append_entry(Message::MeltdownStarted);
+                for direction in CardinalDirection::all() {
+                    self.world.ignite(reactor_coord + direction.coord());
+                }
+                self.meltdown = Some(Meltdown::new());
+            }
+            _ => self.win_countdown = Some((Duration::from_secs(2), Ending::Sacrificed)),
+        }
+    }
+
+    fn meltdown_ending(&self) -> Option<Ending> {
+        let meltdown = self.meltdown?;
+        if self.is_game_over() || meltdown.turns_remaining == 0 {
+            Some(Ending::Sacrificed)
+        } else if self.world.is_elevator_at_coord(self.player_coord()) {
+            Some(Ending::Survived)
+        } else {
+            None
+        }
+    }
 
+    /// Moves the player down to a newly generated floor. If the floor can't be generated, the game
+    /// is left as it was so that the player can try the stairs again.
+    pub fn generate_level(&mut self) -> Result<(), GenerationError> {
         let mut player_data = self.world.clone_entity_data(self.player_entity);
         for weapon in player_data.player.as_mut().unwrap().ranged_weapons.iter_mut() {
             if let Some(weapon) = weapon.as_mut() {
@@ -86,18 +138,27 @@ impl Game {
             ammo.current = ammo.max;
         }
 
-        let Terrain { world, agents, player_entity } =
-            terrain::build_station(&mut self.terrain_state, self.world.level + 1, Some(player_data));
+        // Keycards only open doors on the floor they were found on
+        player_data.player.as_mut().unwrap().clearance = None;
+
+        let level = self.world.level + 1;
+        let Terrain { world, agents, player_entity, objectives, metadata } =
+            terrain::build_station(&mut self.terrain_state, level, Some(player_data))?;
+
+        crate::log::append_entry(Message::Descend);
 
         self.visibility_grid = VisibilityGrid::new(world.size());
         self.behavior_context = BehaviourContext::new(world.size());
 
         self.world = world;
         self.agents = agents;
+        self.objectives = objectives;
+        self.level_metadata = metadata;
         self.player_entity = player_entity;
 
         self.prime_npcs();
         self.update_visibility();
         self.set_new_music();
+        Ok(())
     }
 }
@@ -1,6 +1,8 @@
 mod ai;
 mod behavior;
 mod flow;
+mod loot;
+mod lore;
 mod player;
 mod prompt;
 mod sound;
@@ -33,6 +35,8 @@ pub mod prelude {
     pub use crate::event::*;
     pub use crate::flow::*;
     pub use crate::log::*;
+    pub use crate::loot::*;
+    pub use crate::lore::*;
     pub use crate::player::*;
     pub use crate::prompt::*;
     pub use crate::sound::*;
@@ -69,7 +73,11 @@ pub struct Game {
     pub player_entity: Entity,
 
     pub turn_state: TurnState,
-    pub win_countdown: Option<Duration>,
+    pub win_countdown: Option<(Duration, Ending)>,
+    pub meltdown: Option<Meltdown>,
+    pub objectives: Vec<Objective>,
+    #[serde(default)]
+    pub level_metadata: LevelMetadata,
     pub terrain_state: TerrainState,
     pub agents: ComponentTable<Agent>,
     pub behavior_context: BehaviourContext,
@@ -88,7 +96,8 @@ impl Game {
 
         let mut terrain_state = TerrainState::new();
 
-        let Terrain { player_entity, world, agents } = terrain::build_station(&mut terrain_state, 0, None);
+        let Terrain { player_entity, world, agents, objectives, metadata } =
+            terrain::build_station(&mut terrain_state, 0, None).expect("the first floor is authored");
         let visibility_grid = VisibilityGrid::new(world.size());
         let behavior_context = BehaviourContext::new(world.size());
 
@@ -103,6 +112,9 @@ impl Game {
             visibility_grid,
             behavior_context,
             win_countdown: None,
+            meltdown: None,
+            objectives,
+            level_metadata: metadata,
             turn_state: TurnState::PlayerTurn,
             since_last_frame: Duration::from_millis(0),
             animation_context: AnimationContext::default(),
@@ -134,6 +146,8 @@ impl Game {
         if (!self.world.is_gameplay_blocked() || self.win_countdown.is_some())
             && self.turn_state == TurnState::EnemyTurn
         {
+            self.world.drain_flashlight(self.player_entity);
+            self.world.vacuum_turn(self.player_entity);
             self.npc_turn();
             self.turn_state = TurnState::PlayerTurn;
 
@@ -152,6 +166,19 @@ impl Game {
                             }
                             self.world.components.dead.insert(item_entity, ());
                         }
+                        Item::Keycard(clearance) => {
+                            if let Some(player) = self.world.components.player.get_mut(self.player_entity) {
+                                crate::log::append_entry(Message::TakeKeycard(*clearance));
+                                crate::event::add_event(ExternalEvent::SoundEffect(SoundEffect::Pickup));
+                                player.clearance = player.clearance.max(Some(*clearance));
+                            }
+                            self.world.components.dead.insert(item_entity, ());
+                        }
+                        Item::DogTags => {
+                            crate::log::append_entry(Message::TakeDogTags);
+                            crate::event::add_event(ExternalEvent::SoundEffect(SoundEffect::Pickup));
+                            self.world.components.dead.insert(item_entity, ());
+                        }
                         Item::Medkit => {
                             self.world.heal_fully(self.player_entity);
                             self.world.components.dead.insert(item_entity, ());
@@ -161,12 +188,57 @@ impl Game {
             }
         }
 
+        self.update_objectives();
         self.world.cull_dead(&mut self.agents);
         self.world.animation_tick(&mut self.animation_context);
         self.update_visibility();
     }
 }
 
+//////////////////////////////////////////////////////////////////////////////////////////
+/// Objectives
+//////////////////////////////////////////////////////////////////////////////////////////
+
+impl Game {
+    /// Marks objectives whose targets are all gone as complete, and hands out their rewards
+    fn update_objectives(&mut self) {
+        let world = &self.world;
+        let completed = self
+            .objectives
+            .iter_mut()
+            .filter(|objective| !objective.complete)
+            .filter(|objective| objective.targets().iter().all(|&target| !world.entity_exists(target)))
+            .map(|objective| {
+                objective.complete = true;
+                (objective.typ, objective.reward)
+            })
+            .collect::<Vec<_>>();
+
+        for (typ, reward) in completed {
+            crate::log::append_entry(Message::ObjectiveComplete(typ));
+            self.give_reward(reward);
+        }
+    }
+
+    fn give_reward(&mut self, reward: Reward) {
+        let player = self.world.components.player.get_mut(self.player_entity).expect("no player");
+        match reward {
+            Reward::Credit(amount) => {
+                crate::log::append_entry(Message::TakeCredit(amount));
+                player.credit += amount;
+            }
+            Reward::Upgrade => match player.available_upgrades().first() {
+                Some(&upgrade) => {
+                    crate::log::append_entry(Message::ObjectiveRewardUpgrade(upgrade));
+                    self.world.unlock_upgrade(self.player_entity, upgrade);
+                }
+                // Nothing left to unlock, so pay out what the cheapest upgrade would have cost
+                None => self.give_reward(Reward::Credit(UpgradeLevel::Level1.cost())),
+            },
+        }
+    }
+}
+
 //////////////////////////////////////////////////////////////////////////////////////////
 /// Spatial
 //////////////////////////////////////////////////////////////////////////////////////////
@@ -193,6 +265,11 @@ impl Game {
     }
 
     pub fn set_new_music(&self) {
+        if let Some(music) = self.level_metadata.music {
+            crate::event::add_event(ExternalEvent::LoopMusic(music));
+            return;
+        }
+
         let mut gameplay_music = crate::sound::GAME_MUSIC.lock();
         let mut rng = crate::rng::RNG.lock();
         gameplay_music.shuffle(&mut *rng);
@@ -15,12 +15,47 @@ pub enum Message {
     PlayerStunned,
     DoomBotExplodes,
     TakeCredit(u32),
+    TakeKeycard(Clearance),
+    TakeDogTags,
     EnemyDies(NpcType),
     EnemyStunned(NpcType),
+    EnemyRepaired(NpcType),
+    EnemyShielded(NpcType),
+    AlarmRaised,
+    CameraDestroyed,
+    AlarmPanelDestroyed,
+    CameraSpottedPlayer,
+    ReactorShielded,
+    ReactorExposed,
+    ReactorPhase(u8),
+    ReactorDestroyed,
+    ShieldGeneratorDestroyed,
+    PowerRelayDestroyed,
+    FlashlightFlat,
+    FlashlightRecharged,
+    GeneratorDestroyed,
+    Blackout,
+    SecretDoorFound,
+    SecretDoorBlownOpen,
+    HullBreach,
+    LowOxygen,
+    Suffocating,
+    ObjectiveComplete(ObjectiveType),
+    ObjectiveRewardUpgrade(Upgrade),
+    MeltdownStarted,
+    MeltdownCountdown(u32),
+    PlayerBurns,
+    WallCollapses,
+    Evacuated,
+    EnemyHacked(NpcType),
+    HackWoreOff(NpcType),
+    AllyHitEnemy { ally: NpcType, enemy: NpcType },
+    EnemyHitAlly { enemy: NpcType, ally: NpcType },
     AlertedNearbyEntities,
     EnemyHitPlayer(NpcType),
     EquipWeapon(WeaponType),
     EnemySlammedIntoWall(NpcType),
+    EnemyFellIntoChasm(NpcType),
     PlayerHitEnemy { enemy: NpcType, weapon: WeaponType },
 }
 
@@ -0,0 +1,143 @@
+use crate::prelude::*;
+use lazy_static::lazy_static;
+use std::ops::RangeInclusive;
+
+// Every loot table in the game. See the comment at the top of the file for the format.
+const TABLES: &str = include_str!("tables.txt");
+
+/// An item which can be found lying around or dropped by a robot
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Loot {
+    Weapon(WeaponType),
+    Medkit,
+    Credit(u32),
+}
+
+impl Loot {
+    pub fn level_cell(self) -> LevelCell {
+        match self {
+            Self::Weapon(weapon) => LevelCell::Weapon(weapon),
+            Self::Medkit => LevelCell::Medkit,
+            Self::Credit(value) => LevelCell::Credit(value),
+        }
+    }
+}
+
+/// What a loot table is rolled for
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LootSource {
+    Floor,
+    DeadEnd,
+    HiddenRoom,
+    Weapon,
+    Drop(NpcType),
+}
+
+struct Entry {
+    loot: Option<Loot>,
+    weight: u32,
+    floors: RangeInclusive<u8>,
+}
+
+fn parse_source(name: &str) -> Result<LootSource, String> {
+    use NpcType::*;
+    let source = match name.split_whitespace().collect::<Vec<_>>().as_slice() {
+        ["floor"] => LootSource::Floor,
+        ["dead_end"] => LootSource::DeadEnd,
+        ["hidden_room"] => LootSource::HiddenRoom,
+        ["weapon"] => LootSource::Weapon,
+        ["drop", robot] => LootSource::Drop(match *robot {
+            "minibot" => MiniBot,
+            "secbot" => SecBot,
+            "robocop" => RoboCop,
+            "doombot" => DoomBot,
+            "turret" => Turret,
+            "repairbot" => RepairBot,
+            "shieldbot" => ShieldBot,
+            "drone" => Drone,
+            "skimmer" => Skimmer,
+            _ => return Err(format!("unknown robot \"{}\"", robot)),
+        }),
+        _ => return Err(format!("unknown table \"{}\"", name)),
+    };
+    Ok(source)
+}
+
+fn parse_loot(item: &str) -> Result<Option<Loot>, String> {
+    use WeaponType::*;
+    if let Some(value) = item.strip_prefix("credit:") {
+        let value = value.parse().map_err(|_| format!("bad credit value \"{}\"", value))?;
+        return Ok(Some(Loot::Credit(value)));
+    }
+    let loot = match item {
+        "nothing" => return Ok(None),
+        "medkit" => Loot::Medkit,
+        "pistol" => Loot::Weapon(Pistol),
+        "rifle" => Loot::Weapon(Rifle),
+        "railgun" => Loot::Weapon(Railgun),
+        "leecher" => Loot::Weapon(Leecher),
+        "fifty_cal" => Loot::Weapon(FiftyCal),
+        "cattle_prod" => Loot::Weapon(CattleProd),
+        "chainsaw" => Loot::Weapon(Chainsaw),
+        _ => return Err(format!("unknown item \"{}\"", item)),
+    };
+    Ok(Some(loot))
+}
+
+fn parse_floors(floors: &str) -> Result<RangeInclusive<u8>, String> {
+    let bad_floors = || format!("bad floors \"{}\"", floors);
+    let (first, last) = floors.split_once('-').ok_or_else(bad_floors)?;
+    let first = first.parse().map_err(|_| bad_floors())?;
+    let last = last.parse().map_err(|_| bad_floors())?;
+    Ok(first..=last)
+}
+
+fn parse_entry(line: &str) -> Result<Entry, String> {
+    let fields = line.split_whitespace().collect::<Vec<_>>();
+    let (item, weight, floors) = match fields.as_slice() {
+        [item, weight] => (item, weight, None),
+        [item, weight, floors] => (item, weight, Some(floors)),
+        _ => return Err("expected an item, a weight and optionally a range of floors".to_string()),
+    };
+    Ok(Entry {
+        loot: parse_loot(item)?,
+        weight: weight.parse().map_err(|_| format!("bad weight \"{}\"", weight))?,
+        floors: floors.map_or(Ok(u8::MIN..=u8::MAX), |floors| parse_floors(floors))?,
+    })
+}
+
+fn parse_tables(text: &str) -> Result<Vec<(LootSource, Vec<Entry>)>, String> {
+    let mut tables: Vec<(LootSource, Vec<Entry>)> = Vec::new();
+    for (index, line) in text.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let with_line_number = |err| format!("line {}: {}", index + 1, err);
+        if let Some(name) = line.strip_prefix('[').and_then(|line| line.strip_suffix(']')) {
+            let source = parse_source(name).map_err(with_line_number)?;
+            if tables.iter().any(|&(existing, _)| existing == source) {
+                return Err(with_line_number(format!("the {} table appears twice", name)));
+            }
+            tables.push((source, Vec::new()));
+        } else if let Some((_, entries)) = tables.last_mut() {
+            entries.push(parse_entry(line).map_err(with_line_number)?);
+        } else {
+            return Err(with_line_number("item before the first table".to_string()));
+        }
+    }
+    Ok(tables)
+}
+
+lazy_static! {
+    static ref LOOT_TABLES: Vec<(LootSource, Vec<Entry>)> =
+        parse_tables(TABLES).unwrap_or_else(|err| panic!("bad loot table: {}", err));
+}
+
+/// Rolls once on the loot table for the source, leaving out items which can't be found on the
+/// given floor. Returns `None` if the roll comes up with nothing, or if there's no such table.
+pub fn roll_loot(source: LootSource, level: u8) -> Option<Loot> {
+    let (_, entries) = LOOT_TABLES.iter().find(|&&(table, _)| table == source)?;
+    let entries = entries.iter().filter(|entry| entry.floors.contains(&level)).collect::<Vec<_>>();
+    crate::rng::choose_weighted(&entries, |entry| entry.weight).and_then(|entry| entry.loot)
+}
@@ -0,0 +1,56 @@
+use crate::prelude::*;
+
+// One file of logs per floor. Each entry starts with a "# Title" line, and a line containing only
+// "---" starts a new page.
+const FLOORS: [&str; 6] = [
+    include_str!("floor_0.txt"),
+    include_str!("floor_1.txt"),
+    include_str!("floor_2.txt"),
+    include_str!("floor_3.txt"),
+    include_str!("floor_4.txt"),
+    include_str!("floor_5.txt"),
+];
+
+/// Identifies a single log among all the logs in the station
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LoreId {
+    pub floor: u8,
+    pub index: u8,
+}
+
+#[derive(Debug, Clone)]
+pub struct LoreEntry {
+    pub title: String,
+    pub pages: Vec<String>,
+}
+
+fn parse_floor(text: &str) -> Vec<LoreEntry> {
+    let mut entries: Vec<LoreEntry> = Vec::new();
+    for line in text.lines() {
+        let line = line.trim();
+        if let Some(title) = line.strip_prefix('#') {
+            entries.push(LoreEntry { title: title.trim().to_string(), pages: vec![String::new()] });
+            continue;
+        }
+        let entry = if let Some(entry) = entries.last_mut() { entry } else { continue };
+        if line == "---" {
+            entry.pages.push(String::new());
+        } else if !line.is_empty() {
+            let page = entry.pages.last_mut().expect("entry has no pages");
+            if !page.is_empty() {
+                page.push(' ');
+            }
+            page.push_str(line);
+        }
+    }
+    entries
+}
+
+/// All the logs which can be found on the given floor
+pub fn floor_entries(level: u8) -> Vec<LoreEntry> {
+    FLOORS.get(level as usize).map_or_else(Vec::new, |text| parse_floor(text))
+}
+
+pub fn entry(id: LoreId) -> Option<LoreEntry> {
+    floor_entries(id.floor).into_iter().nth(id.index as usize)
+}
@@ -41,6 +41,7 @@ impl Game {
             return Ok(None);
         }
 
+        self.world.face(self.player_entity, direction);
         let flow = self.world.character_walk_in_direction(self.player_entity, direction)?;
         self.turn_state = TurnState::EnemyTurn;
         Ok(flow)
@@ -51,6 +52,17 @@ impl Game {
         None
     }
 
+    /// Searching takes a turn, and might turn up a hidden door nearby
+    pub fn player_search(&mut self) -> Option<ControlFlow> {
+        if self.win_countdown.is_some() {
+            return None;
+        }
+
+        self.world.search(self.player_entity);
+        self.turn_state = TurnState::EnemyTurn;
+        None
+    }
+
     pub fn player_get(&mut self) -> Result<Option<ControlFlow>, ActionError> {
         if self.win_countdown.is_some() {
             return Ok(None);
@@ -69,6 +81,14 @@ impl Game {
         ActionError::no_item_there()
     }
 
+    pub fn player_interact(&self) -> Result<Option<ControlFlow>, ActionError> {
+        if self.win_countdown.is_some() {
+            return Ok(None);
+        }
+
+        Ok(Some(ControlFlow::Interact))
+    }
+
     pub fn player_fire(&self, slot: RangedWeaponSlot) -> Result<Option<ControlFlow>, ActionError> {
         if self.win_countdown.is_some() {
             return Ok(None);
@@ -87,14 +107,22 @@ impl Game {
         ActionError::no_weapon_in_slot(slot)
     }
 
+    /// Switching the flashlight on or off doesn't take a turn
+    pub fn player_toggle_flashlight(&mut self) -> Result<Option<ControlFlow>, ActionError> {
+        self.world.toggle_flashlight(self.player_entity)?;
+        Ok(None)
+    }
+
     pub fn player_descend(&mut self) -> Result<Option<ControlFlow>, ActionError> {
         if self.win_countdown.is_some() {
             return Ok(None);
         }
 
         if self.stairs_under_player() {
-            self.generate_level();
-            return Ok(Some(ControlFlow::LevelChange));
+            return match self.generate_level() {
+                Ok(()) => Ok(Some(ControlFlow::LevelChange)),
+                Err(err) => Ok(Some(ControlFlow::GenerationFailed(err))),
+            };
         }
 
         ActionError::can_not_descend()
@@ -49,6 +49,13 @@ where
     RNG.lock().gen::<T>()
 }
 
+/// Chooses an element with probability proportional to its weight, or `None` if the slice is empty
+/// or every weight is zero
+pub fn choose_weighted<T>(slice: &[T], weight: impl Fn(&T) -> u32) -> Option<&T> {
+    let mut rng = RNG.lock();
+    slice.choose_weighted(&mut *rng, weight).ok()
+}
+
 pub fn roll_dice(dice: u32, sides: u32) -> u32 {
     let mut rng = RNG.lock();
     (0..dice).map(|_| rng.gen_range(1..=sides)).sum()
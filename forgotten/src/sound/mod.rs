@@ -12,13 +12,14 @@ lazy_static! {
     ]);
 }
 
-#[derive(Serialize, Deserialize, Clone, Copy)]
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
 pub enum Music {
     Gameplay0,
     Gameplay1,
     Gameplay2,
     Gameplay3,
     Gameplay4,
+    Reactor,
 }
 
 #[derive(Serialize, Deserialize, Clone, Copy, Debug, Hash, PartialEq, Eq)]
@@ -14,6 +14,7 @@ impl FireWeapon {
     }
 
     pub fn commit(self, scope: &mut StateScope, direction: CardinalDirection) -> GameState {
+        scope.0.world.face(scope.0.player_entity, direction);
         scope.0.world.character_fire_bullet(
             scope.0.player_entity,
             scope.0.player_coord() + (direction.coord() * 100),
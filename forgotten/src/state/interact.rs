@@ -0,0 +1,25 @@
+use crate::TurnState;
+
+use super::*;
+
+/// Represents the player choosing a direction to interact with
+#[derive(Debug)]
+pub struct Interact(pub(crate) Private);
+
+impl Interact {
+    pub fn commit(
+        self,
+        scope: &mut StateScope,
+        direction: CardinalDirection,
+    ) -> (GameState, Result<(), ActionError>) {
+        let result = scope.0.world.character_interact(scope.0.player_entity, direction);
+        if result.is_ok() {
+            scope.0.turn_state = TurnState::EnemyTurn;
+        }
+        Running(self.0).handle_control_flow_result(result)
+    }
+
+    pub const fn cancel(self) -> GameState {
+        GameState::Running(Running(self.0))
+    }
+}
@@ -2,12 +2,14 @@ use crate::prelude::*;
 
 mod fire;
 mod get;
+mod interact;
 mod prompt;
 mod running;
 mod upgrade;
 
 pub use fire::*;
 pub use get::*;
+pub use interact::*;
 pub use prompt::*;
 pub use running::*;
 pub use upgrade::*;
@@ -33,12 +35,13 @@ pub(crate) struct Private;
 
 #[derive(Debug)]
 pub enum GameState {
-    Win,
+    Win(Ending),
     GameOver,
     Prompt(Prompt),
     Upgrade(UpgradeState),
     Running(Running),
     FireWeapon(FireWeapon),
+    Interact(Interact),
     GetRangedWeapon(GetRangedWeapon),
     GetMeleeWeapon(GetMeleeWeapon),
 }
@@ -55,8 +58,11 @@ pub struct StateScope(pub crate::Game);
 
 mod game_interface {
     use super::StateScope;
-    use crate::{CharacterInfo, ExternalEvent, Message, Player, Upgrade, VisibleCellData};
-    use gridbugs::{coord_2d::Coord, visible_area_detection::VisibilityGrid};
+    use crate::{
+        CharacterInfo, ExternalEvent, LoreEntry, Meltdown, Message, MotionTracker, Objective, Player,
+        ReactorInfo, Theme, Upgrade, VisibleCellData,
+    };
+    use gridbugs::{coord_2d::Coord, rgb_int::Rgb24, visible_area_detection::VisibilityGrid};
 
     impl StateScope {
         //////////////////////////////
@@ -96,6 +102,43 @@ mod game_interface {
             crate::event::get_events()
         }
 
+        pub fn reactor_info(&self) -> Option<ReactorInfo> {
+            self.0.world.reactor_info()
+        }
+
+        pub const fn meltdown(&self) -> Option<Meltdown> {
+            self.0.meltdown
+        }
+
+        /// The name given to the current floor, if it was authored by hand
+        pub fn level_name(&self) -> Option<&str> {
+            self.0.level_metadata.name.as_deref()
+        }
+
+        pub fn ambient_light(&self) -> Rgb24 {
+            self.0.ambient_light()
+        }
+
+        /// The theme of the current floor, unless it was authored by hand
+        pub const fn theme(&self) -> Option<Theme> {
+            self.0.level_metadata.theme
+        }
+
+        /// Optional objectives on the current floor
+        pub fn objectives(&self) -> &[Objective] {
+            &self.0.objectives
+        }
+
+        /// Every log the player has read, in the order they were found
+        pub fn codex(&self) -> Vec<LoreEntry> {
+            self.player().codex.iter().filter_map(|&id| crate::lore::entry(id)).collect()
+        }
+
+        /// Robots picked up by the motion tracker, if the player has one installed
+        pub fn motion_tracker(&self) -> Option<MotionTracker> {
+            self.0.world.motion_tracker(self.0.player_entity)
+        }
+
         pub fn available_upgrades(&self) -> Vec<Upgrade> {
             let player = self.0.world.components.player.get(self.0.player_entity).expect("no player");
             player.available_upgrades()
@@ -2,7 +2,8 @@ use super::*;
 
 #[derive(Debug)]
 pub struct Prompt {
-    pub(crate) message: String,
+    pub(crate) title: Option<String>,
+    pub(crate) pages: Vec<String>,
     pub(crate) private: Private,
 }
 
@@ -15,7 +16,12 @@ impl Prompt {
         Running(self.private).into_witness()
     }
 
-    pub fn message(&self) -> &str {
-        self.message.as_str()
+    pub fn title(&self) -> Option<&str> {
+        self.title.as_deref()
+    }
+
+    /// The text to show, one page at a time
+    pub fn pages(&self) -> &[String] {
+        self.pages.as_slice()
     }
 }
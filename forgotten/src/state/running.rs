@@ -29,7 +29,17 @@ impl Running {
 
     /// Helper for turning self into a prompt with a given message
     fn into_prompt_witness(self, message: String) -> GameState {
-        GameState::Prompt(Prompt { message, private: self.0 })
+        GameState::Prompt(Prompt { title: None, pages: vec![message], private: self.0 })
+    }
+
+    /// Helper for turning self into a prompt showing the pages of a log
+    fn into_lore_witness(self, id: LoreId) -> GameState {
+        match crate::lore::entry(id) {
+            Some(LoreEntry { title, pages }) => {
+                GameState::Prompt(Prompt { title: Some(title), pages, private: self.0 })
+            }
+            None => self.into_witness(),
+        }
     }
 
     fn into_ranged_witness(self) -> GameState {
@@ -44,6 +54,10 @@ impl Running {
         GameState::FireWeapon(FireWeapon { slot, private: self.0 })
     }
 
+    fn into_interact_witness(self) -> GameState {
+        GameState::Interact(Interact(self.0))
+    }
+
     fn into_upgrade(self) -> GameState {
         GameState::Upgrade(UpgradeState(self.0))
     }
@@ -52,13 +66,19 @@ impl Running {
         match cf {
             None => self.into_witness(),
             Some(control_flow) => match control_flow {
-                ControlFlow::Win => GameState::Win,
+                ControlFlow::Win(ending) => GameState::Win(ending),
                 ControlFlow::LevelChange => self.into_witness(),
+                ControlFlow::GenerationFailed(err) => self.into_prompt_witness(format!(
+                    "The stairs are blocked. Try again in a moment.\n\n(Couldn't generate the next floor: {})",
+                    err
+                )),
                 ControlFlow::GameOver => GameState::GameOver,
                 ControlFlow::GetMelee => self.into_melee_witness(),
                 ControlFlow::GetRanged => self.into_ranged_witness(),
                 ControlFlow::Prompt(message) => self.into_prompt_witness(message),
+                ControlFlow::ReadLore(id) => self.into_lore_witness(id),
                 ControlFlow::FireWeapon(slot) => self.into_fire_witness(slot),
+                ControlFlow::Interact => self.into_interact_witness(),
                 ControlFlow::Upgrade => self.into_upgrade(),
             },
         }
@@ -98,6 +118,10 @@ impl Running {
         self.handle_control_flow(game.0.player_wait())
     }
 
+    pub fn player_search(self, game: &mut StateScope) -> GameState {
+        self.handle_control_flow(game.0.player_search())
+    }
+
     pub fn player_get(self, game: &mut StateScope) -> (GameState, Result<(), ActionError>) {
         self.handle_control_flow_result(game.0.player_get())
     }
@@ -110,6 +134,14 @@ impl Running {
         self.handle_control_flow_result(game.0.player_fire(slot))
     }
 
+    pub fn player_interact(self, game: &StateScope) -> (GameState, Result<(), ActionError>) {
+        self.handle_control_flow_result(game.0.player_interact())
+    }
+
+    pub fn player_toggle_flashlight(self, game: &mut StateScope) -> (GameState, Result<(), ActionError>) {
+        self.handle_control_flow_result(game.0.player_toggle_flashlight())
+    }
+
     pub fn player_descend(self, game: &mut StateScope) -> (GameState, Result<(), ActionError>) {
         self.handle_control_flow_result(game.0.player_descend())
     }
@@ -0,0 +1,227 @@
+use super::{LevelCell, Theme};
+use crate::prelude::*;
+use gridbugs::grid_2d::Grid;
+use std::fmt;
+
+// Authored levels are ASCII maps, optionally preceded by a header of "key: value" lines which is
+// separated from the map by a line containing only "---". Header keys:
+//
+//   name: Docking Bay       shown alongside the floor number
+//   music: gameplay0        one of gameplay0..gameplay4 or reactor
+//   ambient: 75 75 90       red, green and blue of the light in unlit cells
+//
+// Map legend:
+//
+//   Terrain                                Items
+//     #  wall        %  cave wall            m  medkit
+//     .  floor       ,  cave floor           $  credit
+//        floor       "  grass                r  random ranged weapon
+//     +  door        ~  water                1  pistol       5  fifty cal
+//     >  stairs      @  player spawn         2  rifle        6  cattle prod
+//     *  reactor     T  terminal             3  railgun      7  chainsaw
+//     U  upgrade     S  server rack          4  leecher
+//     _  chasm       H  bridge
+//     -  narrow door
+//
+//   Lights                                 Robots
+//     W  white       R  red                  b  MiniBot      h  RepairBot
+//     Y  yellow      G  green                s  SecBot       o  ShieldBot
+//     B  blue                                c  RoboCop      v  Drone
+//                                            d  DoomBot      k  Skimmer
+//                                            t  Turret
+
+/// Settings from the header of an authored level
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct LevelMetadata {
+    pub name: Option<String>,
+    pub music: Option<Music>,
+    pub ambient_light: Option<Rgb24>,
+    /// Only generated floors have a theme
+    #[serde(default)]
+    pub theme: Option<Theme>,
+}
+
+pub struct AuthoredLevel {
+    pub metadata: LevelMetadata,
+    pub map: Grid<LevelCell>,
+}
+
+/// Why an authored level couldn't be read. Lines and columns count from 1.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MapParseError {
+    UnknownGlyph { line: usize, column: usize, glyph: char },
+    UnknownKey { line: usize, key: String },
+    InvalidValue { line: usize, key: String, value: String },
+    MalformedHeader { line: usize },
+    RaggedRow { line: usize, expected: usize, found: usize },
+    EmptyMap,
+}
+
+impl fmt::Display for MapParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::UnknownGlyph { line, column, glyph } => {
+                write!(f, "unknown glyph {:?} at line {}, column {}", glyph, line, column)
+            }
+            Self::UnknownKey { line, key } => write!(f, "unknown header key {:?} on line {}", key, line),
+            Self::InvalidValue { line, key, value } => {
+                write!(f, "invalid value {:?} for {:?} on line {}", value, key, line)
+            }
+            Self::MalformedHeader { line } => write!(f, "expected \"key: value\" on line {}", line),
+            Self::RaggedRow { line, expected, found } => {
+                write!(f, "line {} is {} characters wide but the map is {} wide", line, found, expected)
+            }
+            Self::EmptyMap => write!(f, "the map is empty"),
+        }
+    }
+}
+
+impl std::error::Error for MapParseError {}
+
+fn parse_music(value: &str) -> Option<Music> {
+    Some(match value {
+        "gameplay0" => Music::Gameplay0,
+        "gameplay1" => Music::Gameplay1,
+        "gameplay2" => Music::Gameplay2,
+        "gameplay3" => Music::Gameplay3,
+        "gameplay4" => Music::Gameplay4,
+        "reactor" => Music::Reactor,
+        _ => return None,
+    })
+}
+
+fn parse_colour(value: &str) -> Option<Rgb24> {
+    let channels =
+        value.split_whitespace().map(|channel| channel.parse::<u8>().ok()).collect::<Option<Vec<_>>>()?;
+    match channels[..] {
+        [r, g, b] => Some(Rgb24::new(r, g, b)),
+        _ => None,
+    }
+}
+
+fn parse_header<'a>(lines: impl Iterator<Item = (usize, &'a str)>) -> Result<LevelMetadata, MapParseError> {
+    let mut metadata = LevelMetadata::default();
+    for (line, text) in lines {
+        if text.trim().is_empty() {
+            continue;
+        }
+        let (key, value) = text.split_once(':').ok_or(MapParseError::MalformedHeader { line })?;
+        let (key, value) = (key.trim(), value.trim());
+        let invalid = || MapParseError::InvalidValue { line, key: key.to_string(), value: value.to_string() };
+        match key {
+            "name" => metadata.name = Some(value.to_string()),
+            "music" => metadata.music = Some(parse_music(value).ok_or_else(invalid)?),
+            "ambient" => metadata.ambient_light = Some(parse_colour(value).ok_or_else(invalid)?),
+            _ => return Err(MapParseError::UnknownKey { line, key: key.to_string() }),
+        }
+    }
+    Ok(metadata)
+}
+
+fn parse_glyph(glyph: char) -> Option<LevelCell> {
+    use crate::{NpcType::*, WeaponType::*};
+    use LevelCell::*;
+    Some(match glyph {
+        '#' => Wall,
+        '%' => CaveWall,
+        '.' | ' ' => Floor,
+        ',' => CaveFloor,
+        '"' => Grass,
+        '~' => Water,
+        '_' => Chasm,
+        'H' => Bridge,
+        '+' => Door,
+        '-' => NarrowDoor,
+        '>' => Stairs,
+        '@' => PlayerSpawn,
+        '*' => Reactor,
+        'U' => Upgrade,
+        'S' => ServerRack,
+        'W' => Light(Rgb24::new_grey(255)),
+        'R' => Light(Rgb24::new(255, 0, 0)),
+        'Y' => Light(Rgb24::new(255, 255, 0)),
+        'G' => Light(Rgb24::new(0, 255, 0)),
+        'B' => Light(Rgb24::new(0, 0, 255)),
+        'm' => Medkit,
+        '$' => Credit(2),
+        'r' => super::choose_random_weapon(),
+        '1' => Weapon(Pistol),
+        '2' => Weapon(Rifle),
+        '3' => Weapon(Railgun),
+        '4' => Weapon(Leecher),
+        '5' => Weapon(FiftyCal),
+        '6' => Weapon(CattleProd),
+        '7' => Weapon(Chainsaw),
+        'b' => Npc(MiniBot),
+        's' => Npc(SecBot),
+        'c' => Npc(RoboCop),
+        'd' => Npc(DoomBot),
+        't' => Npc(Turret),
+        'h' => Npc(RepairBot),
+        'o' => Npc(ShieldBot),
+        'v' => Npc(Drone),
+        'k' => Npc(Skimmer),
+        _ => return None,
+    })
+}
+
+// Terminals are numbered in reading order, which decides the log each one holds
+fn parse_map<'a>(lines: impl Iterator<Item = (usize, &'a str)>) -> Result<Grid<LevelCell>, MapParseError> {
+    let rows = lines.filter(|(_, text)| !text.is_empty()).collect::<Vec<_>>();
+    let width = rows.first().ok_or(MapParseError::EmptyMap)?.1.chars().count();
+    let mut map: Grid<LevelCell> = Grid::new_default(Size::new(width as u32, rows.len() as u32));
+    let mut num_terminals = 0;
+    for (y, &(line, text)) in rows.iter().enumerate() {
+        let found = text.chars().count();
+        if found != width {
+            return Err(MapParseError::RaggedRow { line, expected: width, found });
+        }
+        for (x, glyph) in text.chars().enumerate() {
+            let cell = if glyph == 'T' {
+                num_terminals += 1;
+                LevelCell::Terminal(num_terminals - 1)
+            } else {
+                parse_glyph(glyph).ok_or(MapParseError::UnknownGlyph { line, column: x + 1, glyph })?
+            };
+            *map.get_checked_mut(Coord::new(x as i32, y as i32)) = cell;
+        }
+    }
+    Ok(map)
+}
+
+/// Reads an authored level, along with the settings in its header if it has one
+pub fn generate_from_str(s: &str) -> Result<AuthoredLevel, MapParseError> {
+    let lines = s.lines().map(|line| line.trim_end_matches('\r')).enumerate().map(|(i, line)| (i + 1, line));
+    let separator = s.lines().position(|line| line.trim() == "---");
+    let (metadata, map) = match separator {
+        Some(separator) => {
+            (parse_header(lines.clone().take(separator))?, parse_map(lines.skip(separator + 1))?)
+        }
+        None => (LevelMetadata::default(), parse_map(lines)?),
+    };
+    Ok(AuthoredLevel { metadata, map })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn unknown_glyph_reports_line_and_column() {
+        let err = generate_from_str("name: Test\n---\n###\n#?#\n###").err();
+        assert_eq!(err, Some(MapParseError::UnknownGlyph { line: 4, column: 2, glyph: '?' }));
+    }
+
+    #[test]
+    fn malformed_header_is_rejected() {
+        let err = generate_from_str("name Test\n---\n###").err();
+        assert_eq!(err, Some(MapParseError::MalformedHeader { line: 1 }));
+    }
+
+    #[test]
+    fn first_floor_parses() {
+        if let Err(err) = generate_from_str(include_str!("levels/first_floor.txt")) {
+            panic!("first floor failed to parse: {}", err);
+        }
+    }
+}
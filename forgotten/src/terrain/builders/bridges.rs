@@ -0,0 +1,81 @@
+use super::*;
+use gridbugs::{coord_2d::Coord, direction::CardinalDirection, grid_2d::Grid};
+use std::collections::VecDeque;
+
+// Cells which can be walked on without wading or falling
+fn is_dry_land(cell: LevelCell) -> bool {
+    !cell.is_solid() && cell != LevelCell::Water
+}
+
+fn is_spannable(cell: LevelCell) -> bool {
+    matches!(cell, LevelCell::Water | LevelCell::Chasm)
+}
+
+// Every cell which can be reached from the start without crossing water or a chasm
+fn dry_land_reachable(map: &Grid<LevelCell>, start: Coord) -> Grid<bool> {
+    let mut seen = Grid::new_copy(map.size(), false);
+    *seen.get_checked_mut(start) = true;
+    let mut to_visit = vec![start];
+    while let Some(current) = to_visit.pop() {
+        for direction in CardinalDirection::all() {
+            let neighbour_coord = current + direction.coord();
+            if map.get(neighbour_coord).map_or(false, |&cell| is_dry_land(cell))
+                && !*seen.get_checked(neighbour_coord)
+            {
+                *seen.get_checked_mut(neighbour_coord) = true;
+                to_visit.push(neighbour_coord);
+            }
+        }
+    }
+    seen
+}
+
+// Finds the shortest run of water and chasm cells leading from the dry land already reached to
+// some dry land which hasn't been, and returns the cells of that run
+fn shortest_crossing(map: &Grid<LevelCell>, reached: &Grid<bool>) -> Option<Vec<Coord>> {
+    let mut came_from: Grid<Option<Coord>> = Grid::new_copy(map.size(), None);
+    let mut seen = reached.clone();
+    let mut to_visit = reached
+        .enumerate()
+        .filter_map(|(coord, &reached)| if reached { Some(coord) } else { None })
+        .collect::<VecDeque<_>>();
+    while let Some(current) = to_visit.pop_front() {
+        for direction in CardinalDirection::all() {
+            let neighbour_coord = current + direction.coord();
+            let neighbour = if let Some(&cell) = map.get(neighbour_coord) { cell } else { continue };
+            if *seen.get_checked(neighbour_coord) {
+                continue;
+            }
+            if is_dry_land(neighbour) {
+                // The far bank. Only crossing cells are ever explored from, so `current` is the
+                // last cell of the crossing.
+                let mut crossing = vec![current];
+                while let Some(previous) = *came_from.get_checked(*crossing.last().unwrap()) {
+                    if *reached.get_checked(previous) {
+                        break;
+                    }
+                    crossing.push(previous);
+                }
+                return Some(crossing);
+            }
+            if is_spannable(neighbour) {
+                *seen.get_checked_mut(neighbour_coord) = true;
+                *came_from.get_checked_mut(neighbour_coord) = Some(current);
+                to_visit.push_back(neighbour_coord);
+            }
+        }
+    }
+    None
+}
+
+// Builds bridges over water and chasms until all the dry land on the floor is connected to where
+// the player starts. Each bridge takes the shortest crossing to the nearest cut-off piece of land.
+pub fn add_bridges(map: &mut Grid<LevelCell>, player_spawn: Coord) {
+    loop {
+        let reached = dry_land_reachable(map, player_spawn);
+        let crossing = if let Some(crossing) = shortest_crossing(map, &reached) { crossing } else { break };
+        for coord in crossing {
+            *map.get_checked_mut(coord) = LevelCell::Bridge;
+        }
+    }
+}
@@ -0,0 +1,136 @@
+use super::*;
+use gridbugs::{
+    coord_2d::{Axis, Coord, Size},
+    grid_2d::Grid,
+};
+
+// Offices are never narrower than this, not counting their walls
+const MIN_OFFICE_SIZE: i32 = 3;
+// Regions larger than this in either dimension are always split further
+const MAX_OFFICE_SIZE: i32 = 8;
+// The first few splits leave a corridor between the two halves rather than a single wall
+const CORRIDOR_DEPTH: u32 = 2;
+
+// A rectangular region of the map, all of which is floor unless it gets split further
+#[derive(Clone, Copy)]
+struct Region {
+    top_left: Coord,
+    size: Coord,
+}
+
+impl Region {
+    fn length(&self, axis: Axis) -> i32 {
+        self.size.get(axis)
+    }
+
+    fn coords(&self) -> impl Iterator<Item = Coord> {
+        let Region { top_left, size } = *self;
+        (0..size.y).flat_map(move |y| (0..size.x).map(move |x| top_left + Coord::new(x, y)))
+    }
+
+    // Splits the region with a band of the given width perpendicular to the axis, starting at the
+    // given offset along it. Returns the regions either side of the band.
+    fn split(&self, axis: Axis, offset: i32, width: i32) -> (Self, Self) {
+        let first = Region { top_left: self.top_left, size: self.size.set(axis, offset) };
+        let second = Region {
+            top_left: self.top_left + Coord::new_axis(offset + width, 0, axis),
+            size: self.size.set(axis, self.length(axis) - offset - width),
+        };
+        (first, second)
+    }
+}
+
+// Coordinates of the line perpendicular to the axis, which is at the given offset into the region
+fn line(region: Region, axis: Axis, offset: i32) -> Vec<Coord> {
+    let start = region.top_left + Coord::new_axis(offset, 0, axis);
+    (0..region.length(axis.other())).map(|i| start + Coord::new_axis(0, i, axis)).collect()
+}
+
+fn is_floor(map: &Grid<LevelCell>, coord: Coord) -> bool {
+    map.get(coord).map_or(false, |cell| cell.is_floor())
+}
+
+// Puts a door in one of the cells of a wall which has floor on both sides of it
+fn add_door_through(map: &mut Grid<LevelCell>, wall: &[Coord], axis: Axis) {
+    let across = Coord::new_axis(1, 0, axis);
+    let candidates = wall
+        .iter()
+        .cloned()
+        .filter(|&coord| is_floor(map, coord + across) && is_floor(map, coord - across))
+        .collect::<Vec<_>>();
+    if let Some(&coord) = crate::rng::choose(&candidates) {
+        *map.get_checked_mut(coord) = LevelCell::Door;
+    }
+}
+
+// Gives every office along one side of a corridor a door onto it. The `outwards` offset points
+// from the corridor wall into the offices.
+fn add_doors_onto_corridor(map: &mut Grid<LevelCell>, wall: &[Coord], outwards: Coord) {
+    let runs = wall
+        .split(|&coord| !is_floor(map, coord + outwards))
+        .filter_map(|run| crate::rng::choose(run).cloned())
+        .collect::<Vec<_>>();
+    for door in runs {
+        *map.get_checked_mut(door) = LevelCell::Door;
+    }
+}
+
+// Splits the region in two along its longer side, recursing into each half, and then joins the
+// halves together. Regions which become offices are added to `offices`.
+fn partition(map: &mut Grid<LevelCell>, region: Region, depth: u32, offices: &mut Vec<Region>) {
+    let axis = if region.length(Axis::X) >= region.length(Axis::Y) { Axis::X } else { Axis::Y };
+    let length = region.length(axis);
+    let corridor = depth < CORRIDOR_DEPTH && length >= MIN_OFFICE_SIZE * 2 + 3;
+    let width = if corridor { 3 } else { 1 };
+    let must_split = length > MAX_OFFICE_SIZE;
+    let can_split = length >= MIN_OFFICE_SIZE * 2 + width;
+    if !can_split || (!must_split && crate::rng::range(0..3) == 0) {
+        for coord in region.coords() {
+            *map.get_checked_mut(coord) = LevelCell::Floor;
+        }
+        offices.push(region);
+        return;
+    }
+
+    let offset = crate::rng::range(MIN_OFFICE_SIZE..=(length - MIN_OFFICE_SIZE - width));
+    let (first, second) = region.split(axis, offset, width);
+    partition(map, first, depth + 1, offices);
+    partition(map, second, depth + 1, offices);
+
+    if corridor {
+        for &coord in &line(region, axis, offset + 1) {
+            *map.get_checked_mut(coord) = LevelCell::Floor;
+        }
+        let outwards = Coord::new_axis(1, 0, axis);
+        add_doors_onto_corridor(map, &line(region, axis, offset), -outwards);
+        add_doors_onto_corridor(map, &line(region, axis, offset + 2), outwards);
+    } else {
+        add_door_through(map, &line(region, axis, offset), axis);
+    }
+}
+
+// Represents a level made up of small offices packed together, with corridors running between
+// blocks of offices
+pub struct OfficeLevel {
+    pub map: Grid<LevelCell>,
+    // Location where the player will start
+    pub player_spawn: Coord,
+    // The floor of each office
+    pub offices: Vec<Vec<Coord>>,
+}
+
+impl OfficeLevel {
+    // Randomly generates an office level by recursively partitioning the map
+    pub fn generate(size: Size) -> Self {
+        let mut map = Grid::new_copy(size, LevelCell::Wall);
+        let region = Region { top_left: Coord::new(1, 1), size: size.to_coord().unwrap() - Coord::new(2, 2) };
+        let mut offices = Vec::new();
+        partition(&mut map, region, 0, &mut offices);
+
+        // The player will start in the middle of a randomly-chosen office
+        let office = crate::rng::choose(&offices).unwrap();
+        let player_spawn = office.top_left + office.size / 2;
+        let offices = offices.iter().map(|office| office.coords().collect()).collect();
+        Self { map, player_spawn, offices }
+    }
+}
@@ -21,9 +21,9 @@ struct GameOfLife {
 }
 
 impl GameOfLife {
-    // Initialize state to random values
-    fn new(size: Size) -> Self {
-        let alive = Grid::new_fn(size, |_| crate::rng::gen::<bool>());
+    // Initialize state to random values, with the given proportion of cells alive
+    fn new(size: Size, density: f64) -> Self {
+        let alive = Grid::new_fn(size, |_| crate::rng::gen::<f64>() < density);
         let next = Grid::new_default(size);
         Self { alive, next }
     }
@@ -46,9 +46,9 @@ impl GameOfLife {
 }
 
 // Generate the starting point for the cavern map by running a cell automata for several steps
-fn generate_initial_cavern_map(size: Size) -> Grid<FloorOrWall> {
+fn generate_initial_cavern_map(size: Size, density: f64) -> Grid<FloorOrWall> {
     const NUM_STEPS: usize = 10;
-    let mut game_of_life = GameOfLife::new(size);
+    let mut game_of_life = GameOfLife::new(size, density);
     // This choice of params leads to cavernous regions of living cells
     let params = GameOfLifeParams { survive_min: 4, survive_max: 8, resurrect_min: 5, resurrect_max: 5 };
 
@@ -96,8 +96,9 @@ fn remove_disconnected_walls(map: &mut Grid<FloorOrWall>) {
     }
 }
 
-pub fn generate_cave_map(size: Size) -> Grid<FloorOrWall> {
-    let mut map = generate_initial_cavern_map(size);
+// Generates caverns. Higher densities give larger caverns.
+pub fn generate_cave_map(size: Size, density: f64) -> Grid<FloorOrWall> {
+    let mut map = generate_initial_cavern_map(size, density);
     surround_map_with_walls(&mut map);
     remove_disconnected_walls(&mut map);
     map
@@ -9,14 +9,22 @@ use gridbugs::{
 use rand::Rng;
 use std::collections::HashSet;
 
+mod bridges;
+mod bsp;
 mod cave;
 mod rooms;
+mod tunnels;
+pub use bridges::*;
+pub use bsp::*;
 pub use cave::*;
 pub use rooms::*;
+pub use tunnels::*;
 
-use crate::WeaponType;
+use crate::{Clearance, NpcType, WeaponType};
 
+/// Logs the map at debug level, one character per cell
 pub fn print_map(grid: &Grid<LevelCell>) {
+    let mut text = String::new();
     for row in grid.rows() {
         for &cell in row {
             use LevelCell::*;
@@ -24,14 +32,32 @@ pub fn print_map(grid: &Grid<LevelCell>) {
                 Wall => '#',
                 Floor => '.',
                 Door => '+',
+                NarrowDoor => '-',
+                SecretDoor => 'D',
+                HiddenFloor => ':',
+                LockedDoor(..) => '=',
                 CaveFloor => ',',
                 CaveWall => '%',
                 Grass => '"',
                 Water => '~',
+                Chasm => '_',
+                Bridge => 'H',
                 PlayerSpawn => '@',
                 Stairs => '>',
                 Light(..) => 'L',
                 Reactor => '*',
+                ShieldGenerator => 'G',
+                Generator => 'g',
+                Camera(..) => 'C',
+                AlarmPanel => 'A',
+                Terminal(..) => 'T',
+                PowerSocket => 'S',
+                Keycard(..) => 'k',
+                Medkit => 'm',
+                Credit(..) => '$',
+                ServerRack => '&',
+                Upgrade => 'U',
+                Npc(..) => 'n',
                 Weapon(wpn) => match wpn {
                     WeaponType::CattleProd => '!',
                     WeaponType::Chainsaw => 'c',
@@ -43,10 +69,11 @@ pub fn print_map(grid: &Grid<LevelCell>) {
                     _ => ' ',
                 },
             };
-            print!("{}", ch);
+            text.push(ch);
         }
-        println!();
+        text.push('\n');
     }
+    log::debug!("{}", text);
 }
 
 #[derive(Clone, Copy, PartialEq, Eq)]
@@ -62,25 +89,79 @@ pub enum LevelCell {
     Wall,
     Floor,
     Door,
+    // Heavy robots can't fit through
+    NarrowDoor,
+    // Looks like a wall until it's found
+    SecretDoor,
+    // Floor of a hidden room. Nothing is generated on it other than the room's loot.
+    HiddenFloor,
+    LockedDoor(Clearance),
     CaveFloor,
     CaveWall,
     Grass,
     Water,
+    // Blocks walking, but not flying, shooting or seeing
+    Chasm,
+    // Walkable floor over water or a chasm
+    Bridge,
     PlayerSpawn,
     Stairs,
     Light(Rgb24),
     Reactor,
+    ShieldGenerator,
+    Generator,
+    Camera(CardinalDirection),
+    AlarmPanel,
+    Terminal(u8),
+    PowerSocket,
+    Keycard(Clearance),
     Weapon(WeaponType),
+    Medkit,
+    Credit(u32),
+    ServerRack,
+    Upgrade,
+    Npc(NpcType),
 }
 
 impl LevelCell {
-    const fn is_wall(&self) -> bool {
+    pub const fn is_wall(&self) -> bool {
         matches!(self, Self::Wall | Self::CaveWall)
     }
 
-    const fn is_floor(&self) -> bool {
+    pub const fn is_floor(&self) -> bool {
         matches!(self, Self::Floor | Self::CaveFloor)
     }
+
+    /// Cells which can't be walked through, whatever keycards the player holds
+    pub const fn is_solid(&self) -> bool {
+        matches!(
+            self,
+            Self::Wall
+                | Self::CaveWall
+                | Self::Chasm
+                | Self::Reactor
+                | Self::ShieldGenerator
+                | Self::Generator
+                | Self::Camera(..)
+                | Self::AlarmPanel
+                | Self::Terminal(..)
+                | Self::PowerSocket
+                | Self::ServerRack
+                | Self::Upgrade
+        )
+    }
+
+    pub const fn is_hidden(&self) -> bool {
+        matches!(self, Self::SecretDoor | Self::HiddenFloor)
+    }
+}
+
+// Returns true iff the cell is part of a hidden room or the walls around it, which are left alone
+// so that the room stays hidden
+pub fn is_part_of_hidden_room(map: &Grid<LevelCell>, coord: Coord) -> bool {
+    std::iter::once(coord)
+        .chain(Direction::all().map(|direction| coord + direction.coord()))
+        .any(|coord| map.get(coord).map_or(false, |cell| cell.is_hidden()))
 }
 
 fn is_surrounded_by_walls(map: &Grid<RoomsAndCorridorsCell>, coord: Coord) -> bool {
@@ -107,6 +188,8 @@ pub fn combine_rooms_and_corridors_level_with_cave(
                 }
             }
             RoomsAndCorridorsCell::Stairs => LevelCell::Stairs,
+            RoomsAndCorridorsCell::HiddenFloor => LevelCell::HiddenFloor,
+            RoomsAndCorridorsCell::SecretDoor => LevelCell::SecretDoor,
         },
     })
 }
@@ -174,7 +257,9 @@ pub fn remove_invalid_doors(map: &mut Grid<LevelCell>) {
     }
 }
 
-pub fn add_grass(map: &mut Grid<LevelCell>) {
+// Grows grass on cave floor wherever perlin noise exceeds the threshold, thinning out as the noise
+// gets stronger
+pub fn add_grass(map: &mut Grid<LevelCell>, threshold: f64) {
     let mut rng = crate::rng::RNG.lock();
     let perlin = Perlin2::new(&mut *rng);
     let zoom = 10.;
@@ -183,7 +268,7 @@ pub fn add_grass(map: &mut Grid<LevelCell>) {
             let x = x as f64 / zoom;
             let y = y as f64 / zoom;
             let noise = perlin.noise((x, y));
-            if noise > 0. && rng.gen::<f64>() > noise {
+            if noise > threshold && rng.gen::<f64>() > noise {
                 *cell = LevelCell::Grass;
             }
         }
@@ -192,9 +277,23 @@ pub fn add_grass(map: &mut Grid<LevelCell>) {
     std::mem::drop(rng);
 }
 
+// Returns a grid of booleans, where a true value indicates that a chasm can open up at that
+// location. Chasms use smoother noise than water, so they form fewer, larger rifts.
+pub fn make_chasm_map(size: Size, threshold: f64) -> Grid<bool> {
+    let mut rng = crate::rng::RNG.lock();
+    let perlin = Perlin2::new(&mut *rng);
+    let zoom = 10.;
+    Grid::new_fn(size, |Coord { x, y }| {
+        let x = x as f64 / zoom;
+        let y = y as f64 / zoom;
+        perlin.noise01((x, y)) > threshold
+    })
+}
+
 // Returns a grid of booleans, where a true value indicates that water can spawn at that location.
-// The grid is populated using perlin noise.
-pub fn make_water_map(size: Size) -> Grid<bool> {
+// The grid is populated using perlin noise, and water spawns wherever the noise exceeds the
+// threshold, so lower thresholds give more water.
+pub fn make_water_map(size: Size, threshold: f64) -> Grid<bool> {
     let mut rng = crate::rng::RNG.lock();
     let perlin = Perlin2::new(&mut *rng);
     let zoom = 7.;
@@ -202,7 +301,7 @@ pub fn make_water_map(size: Size) -> Grid<bool> {
         let x = x as f64 / zoom;
         let y = y as f64 / zoom;
         let noise = perlin.noise01((x, y));
-        noise > 0.65
+        noise > threshold
     });
 
     let mut to_visit = map
@@ -6,6 +6,12 @@ use gridbugs::{
 };
 use std::collections::HashSet;
 
+// Hidden rooms are small, and are tucked behind the wall of a room or corridor
+const HIDDEN_ROOM_MIN_SIZE: Size = Size::new_u16(4, 4);
+const HIDDEN_ROOM_MAX_SIZE: Size = Size::new_u16(7, 6);
+const NUM_HIDDEN_ROOM_ATTEMPTS: usize = 30;
+const MAX_HIDDEN_ROOMS: usize = 2;
+
 // An axis-aligned rectangle
 #[derive(Clone, Copy)]
 struct Rect {
@@ -60,6 +66,27 @@ impl Rect {
     fn centre(&self) -> Coord {
         self.top_left + (self.size / 2)
     }
+
+    // Returns the direction pointing out of the rectangle from an edge coordinate which isn't a
+    // corner, or `None` for corners and coordinates not on the edge
+    fn outward_direction(&self, coord: Coord) -> Option<CardinalDirection> {
+        let bottom_right = self.bottom_right();
+        match (coord.x, coord.y) {
+            (x, y) if x == self.top_left.x && y != self.top_left.y && y != bottom_right.y => {
+                Some(CardinalDirection::West)
+            }
+            (x, y) if x == bottom_right.x && y != self.top_left.y && y != bottom_right.y => {
+                Some(CardinalDirection::East)
+            }
+            (x, y) if y == self.top_left.y && x != self.top_left.x && x != bottom_right.x => {
+                Some(CardinalDirection::North)
+            }
+            (x, y) if y == bottom_right.y && x != self.top_left.x && x != bottom_right.x => {
+                Some(CardinalDirection::South)
+            }
+            _ => None,
+        }
+    }
 }
 
 // Represents a room during terrain generation
@@ -218,6 +245,27 @@ impl RoomPlacement {
         new_room.add_floor_to_map(&mut self.map);
         self.rooms.push(new_room);
     }
+
+    // Adds a room with no corridors, reached through a single secret door in its wall from an
+    // existing room or corridor. Returns the coordinate of the secret door, or `None` if the room
+    // overlaps with the floor or there's nowhere for the door.
+    fn try_add_hidden_room(&mut self, new_room: Room) -> Option<Coord> {
+        if new_room.overlaps_with_floor(&self.map) {
+            return None;
+        }
+        let door_candidates = new_room
+            .rect
+            .edge_coords()
+            .filter(|&coord| {
+                new_room.rect.outward_direction(coord).map_or(false, |direction| {
+                    self.map.get(coord + direction.coord()) == Some(&FloorOrWall::Floor)
+                })
+            })
+            .collect::<Vec<_>>();
+        let &door = crate::rng::choose(&door_candidates)?;
+        new_room.add_floor_to_map(&mut self.map);
+        Some(door)
+    }
 }
 
 // A cell of the RoomsAndCorridorsLevel map
@@ -227,6 +275,8 @@ pub enum RoomsAndCorridorsCell {
     Wall,
     Door,
     Stairs,
+    HiddenFloor,
+    SecretDoor,
 }
 
 // Represents a level made up of rooms and corridors
@@ -235,23 +285,37 @@ pub struct RoomsAndCorridorsLevel {
     pub map: Grid<RoomsAndCorridorsCell>,
     // Location where the player will start
     pub player_spawn: Coord,
+    // The floor of each room, not counting hidden rooms
+    pub rooms: Vec<Vec<Coord>>,
+    // The floor of each room which can only be reached through a secret door
+    pub hidden_rooms: Vec<Vec<Coord>>,
 }
 
 impl RoomsAndCorridorsLevel {
-    // Randomly generates a level made up of rooms and corridors
-    pub fn generate(size: Size) -> Self {
-        const NUM_ROOM_ATTEMPTS: usize = 50;
-        const MIN_ROOM_SIZE: Size = Size::new_u16(5, 5);
-        const MAX_ROOM_SIZE: Size = Size::new_u16(11, 9);
-
+    // Randomly generates a level made up of rooms and corridors. Each attempt places a room of a
+    // random size unless it would overlap one placed before.
+    pub fn generate(size: Size, num_room_attempts: usize, min_room_size: Size, max_room_size: Size) -> Self {
         let mut room_placement = RoomPlacement::new(size);
 
         // Add all the rooms and corridors
-        for _ in 0..NUM_ROOM_ATTEMPTS {
-            let new_room = Room { rect: Rect::choose(size, MIN_ROOM_SIZE, MAX_ROOM_SIZE) };
+        for _ in 0..num_room_attempts {
+            let new_room = Room { rect: Rect::choose(size, min_room_size, max_room_size) };
             room_placement.try_add_room(new_room);
         }
 
+        // Add a few hidden rooms once every other room is in place, so that no corridor passes
+        // through them
+        let mut hidden_rooms = Vec::new();
+        for _ in 0..NUM_HIDDEN_ROOM_ATTEMPTS {
+            if hidden_rooms.len() == MAX_HIDDEN_ROOMS {
+                break;
+            }
+            let new_room = Room { rect: Rect::choose(size, HIDDEN_ROOM_MIN_SIZE, HIDDEN_ROOM_MAX_SIZE) };
+            if let Some(door) = room_placement.try_add_hidden_room(new_room) {
+                hidden_rooms.push((new_room, door));
+            }
+        }
+
         // The player will start in the centre of a randomly-chosen room
         let player_spawn = crate::rng::choose(&room_placement.rooms).unwrap().rect.centre();
 
@@ -269,6 +333,20 @@ impl RoomsAndCorridorsLevel {
             }
         }
 
-        Self { map, player_spawn }
+        let hidden_rooms = hidden_rooms
+            .into_iter()
+            .map(|(room, door)| {
+                *map.get_checked_mut(door) = RoomsAndCorridorsCell::SecretDoor;
+                let floor = room.rect.internal_coords().collect::<Vec<_>>();
+                for &coord in &floor {
+                    *map.get_checked_mut(coord) = RoomsAndCorridorsCell::HiddenFloor;
+                }
+                floor
+            })
+            .collect();
+
+        let rooms = room_placement.rooms.iter().map(|room| room.rect.internal_coords().collect()).collect();
+
+        Self { map, player_spawn, rooms, hidden_rooms }
     }
 }
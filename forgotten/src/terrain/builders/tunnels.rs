@@ -0,0 +1,70 @@
+use super::*;
+use gridbugs::{
+    coord_2d::{Coord, Size},
+    direction::{CardinalDirection, Direction},
+    grid_2d::Grid,
+};
+
+// Number of steps each digger takes before a new one starts somewhere else in the tunnels
+const DIGGER_LIFETIME: usize = 100;
+// Chance out of 100 that a digger turns on each step. Lower values give longer, straighter tunnels.
+const TURN_PERCENT: u32 = 25;
+
+// Represents a level made of winding maintenance tunnels dug by a drunkard's walk
+pub struct TunnelLevel {
+    pub map: Grid<LevelCell>,
+    // Location where the player will start
+    pub player_spawn: Coord,
+}
+
+impl TunnelLevel {
+    // Randomly generates a tunnel level. Diggers start from the middle of the map, and then from
+    // random points in the existing tunnels, until the given fraction of the map has been dug out.
+    pub fn generate(size: Size, floor_fraction: f64) -> Self {
+        let mut map = Grid::new_copy(size, LevelCell::Wall);
+        let player_spawn = size.to_coord().unwrap() / 2;
+        *map.get_checked_mut(player_spawn) = LevelCell::Floor;
+        let mut dug = vec![player_spawn];
+        let target = (size.count() as f64 * floor_fraction) as usize;
+
+        while dug.len() < target {
+            let mut coord = *crate::rng::choose(&dug).unwrap();
+            let mut direction: CardinalDirection = crate::rng::gen();
+            for _ in 0..DIGGER_LIFETIME {
+                if crate::rng::range(0..100) < TURN_PERCENT {
+                    direction = crate::rng::gen();
+                }
+                let next = coord + direction.coord();
+                // Leave the outermost cells as wall
+                let on_map =
+                    next.x > 0 && next.y > 0 && next.x < size.x() as i32 - 1 && next.y < size.y() as i32 - 1;
+                if !on_map {
+                    direction = direction.opposite();
+                    continue;
+                }
+                coord = next;
+                let cell = map.get_checked_mut(coord);
+                if *cell == LevelCell::Wall {
+                    *cell = LevelCell::Floor;
+                    dug.push(coord);
+                }
+            }
+        }
+
+        // Rock that the tunnels don't touch is left as cave wall
+        let solid_rock = map
+            .enumerate()
+            .filter(|&(coord, cell)| {
+                cell.is_wall()
+                    && Direction::all()
+                        .all(|direction| map.get(coord + direction.coord()).map_or(true, |c| c.is_wall()))
+            })
+            .map(|(coord, _)| coord)
+            .collect::<Vec<_>>();
+        for coord in solid_rock {
+            *map.get_checked_mut(coord) = LevelCell::CaveWall;
+        }
+
+        Self { map, player_spawn }
+    }
+}
@@ -0,0 +1,102 @@
+use super::{LevelBuild, LevelCell};
+use crate::prelude::*;
+use gridbugs::grid_2d::Grid;
+use std::collections::HashSet;
+
+/// Robots standing within this many steps of a door are guarding it
+const GUARD_POST_DISTANCE: i32 = 2;
+
+/// The part of the floor a cell is in, which decides what is placed there
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Zone {
+    /// A room with more than one way in
+    Room,
+    /// A room with only one way in, where loot is stashed
+    DeadEnd,
+    /// A passage a single cell wide, which robots patrol
+    Corridor,
+    /// Caverns, and anywhere else that isn't laid out as a room or corridor
+    Open,
+}
+
+/// The rooms, corridors and doors of a generated floor, kept once the floor is finished so that
+/// items and robots can be placed according to what each part of it is for
+pub struct FloorPlan {
+    zones: Grid<Zone>,
+    guard_posts: Grid<bool>,
+    exit: Option<Coord>,
+}
+
+fn is_solid(map: &Grid<LevelCell>, coord: Coord) -> bool {
+    map.get(coord).map_or(true, |cell| cell.is_solid())
+}
+
+// A corridor cell has something solid either side of it along exactly one axis
+fn is_corridor(map: &Grid<LevelCell>, coord: Coord) -> bool {
+    let solid_either_side = |offset: Coord| is_solid(map, coord + offset) && is_solid(map, coord - offset);
+    map.get_checked(coord).is_floor()
+        && solid_either_side(Coord::new(1, 0)) != solid_either_side(Coord::new(0, 1))
+}
+
+// Counts the cells around the edge of a room which the player could walk through to get in. Caves
+// which break through a wall add several, so only rooms left intact can be dead ends.
+fn count_entrances(map: &Grid<LevelCell>, room: &[Coord]) -> usize {
+    let floor = room.iter().cloned().collect::<HashSet<_>>();
+    room.iter()
+        .flat_map(|&coord| CardinalDirection::all().map(move |direction| coord + direction.coord()))
+        .filter(|coord| !floor.contains(coord) && !is_solid(map, *coord))
+        .collect::<HashSet<_>>()
+        .len()
+}
+
+impl FloorPlan {
+    pub fn new(build: &LevelBuild) -> Self {
+        let map = &build.map;
+        let outside_rooms = |coord| if is_corridor(map, coord) { Zone::Corridor } else { Zone::Open };
+        let mut zones = Grid::new_fn(map.size(), outside_rooms);
+        for room in &build.rooms {
+            let zone = if count_entrances(map, room) == 1 { Zone::DeadEnd } else { Zone::Room };
+            for &coord in room {
+                *zones.get_checked_mut(coord) = zone;
+            }
+        }
+
+        let mut guard_posts = Grid::new_copy(map.size(), false);
+        for (door, cell) in map.enumerate() {
+            if !matches!(cell, LevelCell::Door | LevelCell::NarrowDoor | LevelCell::LockedDoor(..)) {
+                continue;
+            }
+            for y in -GUARD_POST_DISTANCE..=GUARD_POST_DISTANCE {
+                for x in -GUARD_POST_DISTANCE..=GUARD_POST_DISTANCE {
+                    if x.abs() + y.abs() <= GUARD_POST_DISTANCE {
+                        if let Some(guard_post) = guard_posts.get_mut(door + Coord::new(x, y)) {
+                            *guard_post = true;
+                        }
+                    }
+                }
+            }
+        }
+
+        Self { zones, guard_posts, exit: build.exit() }
+    }
+
+    pub fn zone(&self, coord: Coord) -> Zone {
+        *self.zones.get_checked(coord)
+    }
+
+    pub fn is_guard_post(&self, coord: Coord) -> bool {
+        *self.guard_posts.get_checked(coord)
+    }
+
+    /// Manhattan distance to the exit. Everywhere is infinitely far from an exit that doesn't exist.
+    pub fn distance_to_exit(&self, coord: Coord) -> u32 {
+        self.exit.map_or(u32::MAX, |exit| coord.manhattan_distance(exit))
+    }
+}
+
+/// Removes and returns the last candidate which satisfies the predicate, or the last candidate if
+/// none do, so that content still gets placed on floors without the right kind of space
+pub fn take_preferring(candidates: &mut Vec<Coord>, prefer: impl FnMut(&Coord) -> bool) -> Option<Coord> {
+    let index = candidates.iter().rposition(prefer).or_else(|| candidates.len().checked_sub(1))?;
+    Some(candidates.swap_remove(index))
+}
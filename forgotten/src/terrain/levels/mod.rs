@@ -1,12 +1,13 @@
 use gridbugs::grid_2d::Grid;
 
-use crate::{prelude::*, terrain::procgen};
+use crate::prelude::*;
 
 pub fn spawn_terrain(
     grid: Grid<LevelCell>,
     world: &mut World,
+    agents: &mut ComponentTable<Agent>,
     player_data: Option<EntityData>,
-) -> (Entity, Vec<Coord>) {
+) -> Result<(Entity, Vec<Coord>), CellOccupied> {
     let mut player_entity = None;
     let mut empty_coords = Vec::new();
     for (coord, cell) in grid.enumerate() {
@@ -14,66 +15,134 @@ pub fn spawn_terrain(
 
         match cell {
             Floor => {
-                world.spawn_floor(coord);
+                world.spawn_floor(coord)?;
                 empty_coords.push(coord);
             }
             CaveFloor => {
-                world.spawn_cave_floor(coord);
+                world.spawn_cave_floor(coord)?;
                 empty_coords.push(coord);
             }
-            Wall => world.spawn_wall(coord),
-            CaveWall => world.spawn_cave_wall(coord),
+            Wall => world.spawn_wall(coord)?,
+            CaveWall => world.spawn_cave_wall(coord)?,
             Door => {
-                world.spawn_floor(coord);
-                world.spawn_door(coord);
+                world.spawn_floor(coord)?;
+                world.spawn_door(coord)?;
+            }
+            NarrowDoor => {
+                world.spawn_floor(coord)?;
+                world.spawn_narrow_door(coord)?;
+            }
+            SecretDoor => {
+                world.spawn_floor(coord)?;
+                world.spawn_secret_door(coord)?;
+            }
+            // Nothing else is spawned in hidden rooms, so they aren't offered as empty cells
+            HiddenFloor => world.spawn_floor(coord)?,
+            LockedDoor(clearance) => {
+                world.spawn_floor(coord)?;
+                world.spawn_locked_door(coord, *clearance)?;
+            }
+            Keycard(clearance) => {
+                world.spawn_floor(coord)?;
+                world.spawn_keycard(coord, *clearance)?;
             }
             Grass => {
-                world.spawn_cave_floor(coord);
-                world.spawn_grass(coord);
+                world.spawn_cave_floor(coord)?;
+                world.spawn_grass(coord)?;
             }
             Water => {
-                world.spawn_water(coord);
+                world.spawn_water(coord)?;
             }
+            Chasm => world.spawn_chasm(coord)?,
+            Bridge => world.spawn_bridge(coord)?,
             PlayerSpawn => {
-                world.spawn_floor(coord);
+                world.spawn_floor(coord)?;
 
                 if let Some(ref player_data) = player_data {
                     let location = Location { coord, layer: Some(Layer::Character) };
                     player_entity = Some(world.insert_entity_data(location, player_data.clone()));
                 } else {
-                    player_entity = Some(world.spawn_player(coord));
+                    player_entity = Some(world.spawn_player(coord)?);
+                }
+
+                // The player is extracted from where they arrived once the reactor is destroyed
+                if world.level == FINAL_LEVEL {
+                    world.spawn_elevator(coord)?;
                 }
             }
             Stairs => {
-                world.spawn_floor(coord);
-                world.spawn_stairs(coord);
+                world.spawn_floor(coord)?;
+                world.spawn_stairs(coord)?;
             }
             Light(color) => {
                 world.spawn_light(coord, *color);
-                world.spawn_floor(coord);
+                world.spawn_floor(coord)?;
             }
             Reactor => {
-                world.spawn_reactor(coord);
+                world.spawn_reactor(coord)?;
+            }
+            Generator => {
+                world.spawn_floor(coord)?;
+                world.spawn_generator(coord)?;
+            }
+            ShieldGenerator => {
+                world.spawn_floor(coord)?;
+                world.spawn_shield_generator(coord)?;
+            }
+            Camera(facing) => {
+                world.spawn_camera(coord, *facing)?;
+            }
+            AlarmPanel => {
+                world.spawn_alarm_panel(coord)?;
+            }
+            PowerSocket => {
+                world.spawn_power_socket(coord)?;
+            }
+            Terminal(index) => {
+                world.spawn_terminal(coord, LoreId { floor: world.level, index: *index })?;
             }
             Weapon(wpn) => {
-                world.spawn_floor(coord);
-                world.spawn_weapon(coord, *wpn);
+                world.spawn_floor(coord)?;
+                world.spawn_weapon(coord, *wpn)?;
+            }
+            Medkit => {
+                world.spawn_floor(coord)?;
+                world.spawn_medkit(coord)?;
+            }
+            Credit(value) => {
+                world.spawn_floor(coord)?;
+                world.spawn_credit(coord, *value)?;
+            }
+            Upgrade => {
+                world.spawn_floor(coord)?;
+                world.spawn_upgrade(coord)?;
+            }
+            ServerRack => {
+                world.spawn_floor(coord)?;
+                world.spawn_server_rack(coord)?;
+            }
+            Npc(npc_type) => {
+                world.spawn_floor(coord)?;
+                let npc = world.spawn_npc(coord, *npc_type)?;
+                agents.insert(npc, Agent::new(world.size(), *npc_type));
             }
         }
     }
 
-    (player_entity.expect("didn't create player"), empty_coords)
+    Ok((player_entity.expect("didn't create player"), empty_coords))
 }
 
 pub fn first_floor() -> Terrain {
     const LEVEL: u8 = 0;
 
-    let grid = procgen::generate_from_str(include_str!("first_floor.txt"));
+    let AuthoredLevel { metadata, map: grid } = generate_from_str(include_str!("first_floor.txt"))
+        .unwrap_or_else(|err| panic!("bad first floor: {}", err));
     // let grid = procgen::generate(Size::new_u16(30, 30), 0);
     let mut world = World::new(Size::new_u16(40, 33), LEVEL);
 
-    let agents = ComponentTable::default();
-    let (player_entity, _) = spawn_terrain(grid, &mut world, None);
+    let mut agents = ComponentTable::default();
+    let (player_entity, _) = spawn_terrain(grid, &mut world, &mut agents, None)
+        .unwrap_or_else(|err| panic!("bad first floor: {}", err));
 
-    Terrain { world, player_entity, agents }
+    Terrain { world, player_entity, agents, objectives: Vec::new(), metadata }
 }
@@ -1,82 +1,136 @@
-use std::collections::HashSet;
-
 use crate::prelude::*;
 
+mod authored;
 mod builders;
+mod floor_plan;
 mod levels;
+mod pipeline;
 mod procgen;
+mod theme;
+mod validation;
+mod vaults;
 
+pub use authored::*;
 pub use builders::*;
+pub use floor_plan::*;
 pub use levels::*;
+pub use pipeline::*;
 pub use procgen::*;
-use rand::seq::SliceRandom;
+pub use theme::*;
+pub use validation::*;
 
 pub const FINAL_LEVEL: u8 = 5;
+/// Items and robots are placed further than this from where the player arrives
+const MIN_ITEM_DISTANCE: u32 = 12;
+/// Floors which fail validation are regenerated up to this many times
+const MAX_GENERATION_ATTEMPTS: usize = 20;
 
 /// The output of terrain generation
 pub struct Terrain {
     pub world: World,
     pub player_entity: Entity,
     pub agents: ComponentTable<Agent>,
+    pub objectives: Vec<Objective>,
+    pub metadata: LevelMetadata,
 }
 
+/// Choices made at the start of a run which shape every generated floor. Weapons and other items
+/// come from the loot tables.
 #[derive(Default, Clone, Debug, Serialize, Deserialize)]
 pub struct TerrainState {
-    chainsaw_floors: HashSet<u8>,
-    cattle_prod_floors: HashSet<u8>,
-    ranged_weapons: Vec<WeaponType>,
+    #[serde(default)]
+    themes: Vec<Theme>,
 }
 
 impl TerrainState {
     pub fn new() -> Self {
-        use crate::WeaponType::*;
-        let mut rng = crate::rng::RNG.lock();
-
-        let mut ranged_weapons = vec![
-            Rifle, Railgun, Leecher, Pistol, FiftyCal, Rifle, Railgun, Leecher, Pistol, Pistol, FiftyCal,
-        ];
-        ranged_weapons.shuffle(&mut *rng);
-
-        let mut floors = (1..=5).collect::<Vec<_>>();
-        floors.shuffle(&mut *rng);
-
-        let mut cattle_prod_floors = HashSet::new();
-        for _ in 0..2 {
-            cattle_prod_floors.insert(floors.pop().unwrap());
-        }
-
-        let mut chainsaw_floors = HashSet::new();
-        for _ in 0..2 {
-            chainsaw_floors.insert(floors.pop().unwrap());
-        }
+        Self { themes: Theme::choose_for_run() }
+    }
 
-        Self { ranged_weapons, chainsaw_floors, cattle_prod_floors }
+    /// The theme chosen for a generated floor at the start of the run
+    pub fn theme(&self, level: u8) -> Theme {
+        self.themes.get(level as usize - 1).copied().unwrap_or(if level == FINAL_LEVEL {
+            Theme::ReactorCore
+        } else {
+            Theme::Barracks
+        })
     }
 }
 
+/// Builds a floor of the station. Generated floors which fail validation, or which can't be spawned
+/// because two things ended up in the same cell, are thrown away and generated again. An error is
+/// only returned if every attempt fails.
 pub fn build_station(
     terrain_state: &mut TerrainState,
     level: u8,
     player_data: Option<EntityData>,
-) -> Terrain {
+) -> Result<Terrain, GenerationError> {
     if level == 0 {
-        return first_floor();
+        return Ok(first_floor());
     } else if level == FINAL_LEVEL {
-        println!("Generating last floor");
+        log::debug!("Generating last floor");
     }
 
     const STATION_SIZE: Size = Size::new_u16(40, 33);
 
-    let grid = procgen::generate(STATION_SIZE, level);
+    let theme = terrain_state.theme(level);
+    let mut attempts = 0;
+    loop {
+        attempts += 1;
+        let result = procgen::generate(STATION_SIZE, level, theme)
+            .and_then(|build| populate(build, STATION_SIZE, level, theme, player_data.clone()));
+        match result {
+            Ok(terrain) => return Ok(terrain),
+            Err(err) if attempts < MAX_GENERATION_ATTEMPTS => {
+                log::warn!("Regenerating floor {}: {}", level, err)
+            }
+            Err(err) => return Err(GenerationError::Exhausted { attempts, last: Box::new(err) }),
+        }
+    }
+}
+
+// Spawns the generated floor into a new world along with its items, robots and objectives
+fn populate(
+    build: LevelBuild,
+    size: Size,
+    level: u8,
+    theme: Theme,
+    player_data: Option<EntityData>,
+) -> Result<Terrain, GenerationError> {
+    let dry = dry_reachable(&build.map);
+    let plan = FloorPlan::new(&build);
     let mut agents = ComponentTable::default();
-    let mut world = World::new(STATION_SIZE, level);
-    let (player_entity, mut empty_coords) = spawn_terrain(grid, &mut world, player_data);
+    let mut world = World::new(size, level);
+    let (player_entity, mut empty_coords) = spawn_terrain(build.map, &mut world, &mut agents, player_data)?;
 
     let player_coord = world.entity_coord(player_entity).unwrap();
-    empty_coords.retain(|coord| coord.manhattan_distance(player_coord) > 12);
+    empty_coords.retain(|&coord| {
+        *dry.get_checked(coord) && coord.manhattan_distance(player_coord) > MIN_ITEM_DISTANCE
+    });
 
-    generate_items(level, &mut world, terrain_state, &mut empty_coords);
-    generate_npcs(level, &mut world, &mut empty_coords, &mut agents);
+    generate_items(level, &mut world, &plan, &mut empty_coords)?;
+    generate_turrets(level, &mut world, &mut empty_coords, &mut agents)?;
+    generate_npcs(level, theme, &mut world, &plan, &mut empty_coords, &mut agents)?;
+    let objectives = generate_objectives(level, &mut world, &mut empty_coords, &mut agents)?;
+    world.wire_power_grid();
 
-    Terrain { world, player_entity, agents }
+    Ok(Terrain { world, player_entity, agents, objectives, metadata: theme.metadata() })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn every_seed_generates_every_floor() {
+        for seed in 0..2000 {
+            crate::rng::reseed_u64(seed);
+            let mut terrain_state = TerrainState::new();
+            for level in 1..=FINAL_LEVEL {
+                if let Err(err) = build_station(&mut terrain_state, level, None) {
+                    panic!("seed {} failed to generate floor {}: {}", seed, level, err);
+                }
+            }
+        }
+    }
 }
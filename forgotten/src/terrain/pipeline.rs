@@ -0,0 +1,124 @@
+use super::*;
+use gridbugs::grid_2d::Grid;
+
+/// The floor as it is being generated, handed from one stage of the pipeline to the next
+pub struct LevelBuild {
+    pub level: u8,
+    pub map: Grid<LevelCell>,
+    /// Cells which will be flooded by the `Flood` stage
+    pub water: Grid<bool>,
+    pub player_spawn: Coord,
+    pub stairs: Option<Coord>,
+    pub reactor: Option<Coord>,
+    /// The floor of each room or office laid out by the builders, used to decide what goes where
+    /// once the floor is generated
+    pub rooms: Vec<Vec<Coord>>,
+    /// The floor of each room behind a secret door
+    pub hidden_rooms: Vec<Vec<Coord>>,
+}
+
+impl LevelBuild {
+    fn new(size: Size, level: u8) -> Self {
+        Self {
+            level,
+            map: Grid::new_default(size),
+            water: Grid::new_copy(size, false),
+            player_spawn: Coord::new(size.x() as i32 / 2, size.y() as i32 / 2),
+            stairs: None,
+            reactor: None,
+            rooms: Vec::new(),
+            hidden_rooms: Vec::new(),
+        }
+    }
+
+    pub fn size(&self) -> Size {
+        self.map.size()
+    }
+
+    /// The stairs, or the reactor on the final floor
+    pub fn exit(&self) -> Option<Coord> {
+        if self.level == FINAL_LEVEL {
+            self.reactor
+        } else {
+            self.stairs
+        }
+    }
+}
+
+/// A single step of level generation, such as carving out rooms or placing the stairs
+pub trait LevelBuilder {
+    fn build(&self, build: &mut LevelBuild);
+}
+
+/// A sequence of stages which together generate a floor
+#[derive(Default)]
+pub struct Pipeline {
+    stages: Vec<Box<dyn LevelBuilder>>,
+}
+
+impl Pipeline {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn then<B: LevelBuilder + 'static>(mut self, stage: B) -> Self {
+        self.stages.push(Box::new(stage));
+        self
+    }
+
+    /// Runs each stage in turn, and then checks that the floor is playable
+    pub fn build(&self, size: Size, level: u8) -> Result<LevelBuild, GenerationError> {
+        let mut build = LevelBuild::new(size, level);
+        for stage in &self.stages {
+            stage.build(&mut build);
+        }
+        build.validate()?;
+        Ok(build)
+    }
+}
+
+/// The different ways a floor can be laid out
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Recipe {
+    /// Rooms and corridors broken up by caverns
+    Station,
+    /// Cramped offices with lots of doors
+    OfficeBlock,
+    /// Narrow winding tunnels
+    MaintenanceTunnels,
+}
+
+impl Recipe {
+    // Stages which carve out the shape of the floor come first, then the floor is cleaned up and
+    // everything the player interacts with is added. The theme decides how much of the floor is
+    // cavern, water, chasm and grass.
+    pub fn pipeline(self, theme: Theme) -> Pipeline {
+        let layout = match self {
+            Self::Station => Pipeline::new()
+                .then(RoomsAndCorridors {
+                    num_room_attempts: theme.num_room_attempts(),
+                    ..Default::default()
+                })
+                .then(Caves { density: theme.cave_density() }),
+            Self::OfficeBlock => Pipeline::new().then(Offices),
+            Self::MaintenanceTunnels => Pipeline::new().then(Tunnels::default()),
+        };
+        layout
+            .then(Water { threshold: theme.water_threshold() })
+            .then(Connect)
+            .then(Grass { threshold: theme.grass_threshold() })
+            .then(Vaults)
+            .then(Exit)
+            .then(Flood)
+            .then(Chasms { threshold: theme.chasm_threshold() })
+            .then(Bridges)
+            .then(HiddenLoot)
+            .then(ShieldGenerators)
+            .then(PowerGenerators)
+            .then(Security)
+            .then(Terminals)
+            .then(PowerSockets)
+            .then(NarrowDoors)
+            .then(LockedDoors)
+    }
+}
@@ -1,11 +1,23 @@
-use super::builders;
+use super::{builders, vaults, MIN_ITEM_DISTANCE};
 use crate::prelude::*;
-use gridbugs::{direction::Directions, grid_2d::Grid};
-use rand::seq::SliceRandom;
+use gridbugs::{
+    direction::{CardinalDirection, Directions},
+    grid_2d::Grid,
+};
 
 const DISTANCE_FOR_STAIRS: u32 = 25;
-
-fn choose_stairs_coord(map: &mut Grid<LevelCell>, player_coord: Coord) {
+const DISTANCE_FOR_SECURITY: u32 = 8;
+const LOCKED_DOORS_PER_CLEARANCE: usize = 2;
+const NARROW_DOOR_CHANCE: u8 = 25;
+const NUM_SHIELD_GENERATORS: usize = 3;
+const SHIELD_GENERATOR_MIN_DISTANCE_SQUARED: u32 = 9;
+const SHIELD_GENERATOR_MAX_DISTANCE_SQUARED: u32 = 49;
+const POWER_GENERATOR_MIN_DISTANCE_SQUARED: u32 = 100;
+const NUM_POWER_SOCKETS: usize = 3;
+const NUM_VAULT_ATTEMPTS: usize = 100;
+const MAX_VAULT_CORRIDOR_LENGTH: usize = 8;
+
+fn choose_stairs_coord(map: &mut Grid<LevelCell>, player_coord: Coord) -> Option<Coord> {
     let mut possible_stairs = map
         .enumerate()
         .filter(|(_, cell)| **cell == LevelCell::Floor || **cell == LevelCell::CaveFloor)
@@ -19,11 +31,12 @@ fn choose_stairs_coord(map: &mut Grid<LevelCell>, player_coord: Coord) {
         .collect::<Vec<_>>();
 
     crate::rng::shuffle(&mut possible_stairs);
-    let stairs_coord = possible_stairs.pop().expect("No stairs spots");
+    let stairs_coord = possible_stairs.pop()?;
     *map.get_checked_mut(stairs_coord) = LevelCell::Stairs;
+    Some(stairs_coord)
 }
 
-fn choose_reactor_coord(map: &mut Grid<LevelCell>, player_coord: Coord) {
+fn choose_reactor_coord(map: &mut Grid<LevelCell>, player_coord: Coord) -> Option<Coord> {
     let mut possible_reactors = map
         .enumerate()
         .filter(|(_, cell)| **cell == LevelCell::Floor || **cell == LevelCell::CaveFloor)
@@ -33,140 +46,693 @@ fn choose_reactor_coord(map: &mut Grid<LevelCell>, player_coord: Coord) {
         .collect::<Vec<_>>();
 
     crate::rng::shuffle(&mut possible_reactors);
-    let reactor_coord = possible_reactors.pop().expect("No reactor spots");
+    let reactor_coord = possible_reactors.pop()?;
     *map.get_checked_mut(reactor_coord) = LevelCell::Reactor;
+    Some(reactor_coord)
 }
 
-pub fn choose_random_weapon() -> LevelCell {
-    use crate::WeaponType::*;
-    let mut rng = crate::rng::RNG.lock();
+// Surrounds the reactor with shield generators, keeping them spread apart so they can't all be
+// taken out by a single explosion
+fn add_shield_generators(map: &mut Grid<LevelCell>, reactor_coord: Coord) {
+    let mut candidates = map
+        .enumerate()
+        .filter(|(_, cell)| matches!(cell, LevelCell::Floor | LevelCell::CaveFloor | LevelCell::Grass))
+        .map(|(coord, _)| coord)
+        .filter(|coord| {
+            (SHIELD_GENERATOR_MIN_DISTANCE_SQUARED..=SHIELD_GENERATOR_MAX_DISTANCE_SQUARED)
+                .contains(&coord.distance2(reactor_coord))
+        })
+        .collect::<Vec<_>>();
+    crate::rng::shuffle(&mut candidates);
 
-    let mut ranged_weapons = vec![Rifle, Railgun, Leecher, Pistol, Rifle, Railgun, Leecher, Pistol, Pistol];
-    ranged_weapons.shuffle(&mut *rng);
+    let mut generators: Vec<Coord> = Vec::new();
+    for coord in candidates {
+        if generators.len() == NUM_SHIELD_GENERATORS {
+            break;
+        }
 
-    LevelCell::Weapon(ranged_weapons.pop().unwrap())
+        if generators
+            .iter()
+            .all(|generator| generator.distance2(coord) >= SHIELD_GENERATOR_MIN_DISTANCE_SQUARED)
+        {
+            *map.get_checked_mut(coord) = LevelCell::ShieldGenerator;
+            generators.push(coord);
+        }
+    }
 }
 
-pub fn generate_from_str(s: &str) -> Grid<LevelCell> {
-    let rows = s.split('\n').filter(|s| !s.is_empty()).collect::<Vec<_>>();
-    let size = Size::new_u16(rows[0].len() as u16, rows.len() as u16);
+// Places the generators which power the floor, spread apart so that each one ends up feeding its own
+// wing. Generators only go in open areas so they never block a corridor.
+fn add_power_generators(map: &mut Grid<LevelCell>, level: u8) {
+    let mut candidates = map
+        .enumerate()
+        .filter(|(coord, cell)| {
+            cell.is_floor()
+                && Directions.into_iter().all(|direction| {
+                    map.get(*coord + direction.coord()).map_or(false, |cell| cell.is_floor())
+                })
+        })
+        .map(|(coord, _)| coord)
+        .collect::<Vec<_>>();
+    crate::rng::shuffle(&mut candidates);
 
-    log::info!("Generating map of size {:?}", size);
-    let mut map: Grid<LevelCell> = Grid::new_default(size);
-    for (y, row) in rows.iter().enumerate() {
-        for (x, ch) in row.chars().enumerate() {
-            if ch.is_control() {
-                continue;
-            }
+    let num_generators = 2 + level as usize / 2;
+    let mut generators: Vec<Coord> = Vec::new();
+    for coord in candidates {
+        if generators.len() == num_generators {
+            break;
+        }
 
-            let coord = Coord::new(x as i32, y as i32);
-            let tile = match ch {
-                '.' => LevelCell::Floor,
-                '#' => LevelCell::Wall,
-                '>' => LevelCell::Stairs,
-                '@' => LevelCell::PlayerSpawn,
-                'R' => LevelCell::Light(Rgb24 { r: 255, g: 0, b: 0 }),
-                'r' => choose_random_weapon(),
-                ' ' => LevelCell::Floor,
-                _ => unreachable!("Unknown tile: {}", ch),
-            };
+        if generators
+            .iter()
+            .all(|generator| generator.distance2(coord) >= POWER_GENERATOR_MIN_DISTANCE_SQUARED)
+        {
+            *map.get_checked_mut(coord) = LevelCell::Generator;
+            generators.push(coord);
+        }
+    }
+}
 
-            *map.get_checked_mut(coord) = tile;
+// Returns the direction a piece of wall-mounted equipment placed at the given coord would face. Only
+// walls with exactly one neighbouring floor cell are suitable, so that equipment always faces into a
+// room or along a corridor.
+fn wall_mount_facing(map: &Grid<LevelCell>, coord: Coord) -> Option<CardinalDirection> {
+    if *map.get_checked(coord) != LevelCell::Wall {
+        return None;
+    }
+
+    let mut floor_directions = CardinalDirection::all()
+        .filter(|direction| map.get(coord + direction.coord()).map_or(false, |cell| cell.is_floor()));
+    let facing = floor_directions.next()?;
+    if floor_directions.next().is_some() {
+        return None;
+    }
+
+    Some(facing)
+}
+
+// Mounts security cameras and alarm panels on the walls of rooms and corridors
+fn add_security(map: &mut Grid<LevelCell>, player_coord: Coord, level: u8) {
+    let mut candidates = map
+        .coord_iter()
+        .filter(|coord| coord.manhattan_distance(player_coord) > DISTANCE_FOR_SECURITY)
+        .filter_map(|coord| wall_mount_facing(map, coord).map(|facing| (coord, facing)))
+        .collect::<Vec<_>>();
+    crate::rng::shuffle(&mut candidates);
+
+    let num_cameras = 1 + level as usize / 2;
+    for _ in 0..num_cameras {
+        if let Some((coord, facing)) = candidates.pop() {
+            *map.get_checked_mut(coord) = LevelCell::Camera(facing);
         }
     }
 
-    map
+    // Alarm panels are only useful on floors which RoboCops patrol
+    if level >= 3 {
+        if let Some((coord, _)) = candidates.pop() {
+            *map.get_checked_mut(coord) = LevelCell::AlarmPanel;
+        }
+    }
 }
 
-pub fn generate(size: Size, level: u8) -> Grid<LevelCell> {
-    let RoomsAndCorridorsLevel { map: rooms_and_corridors_map, player_spawn } =
-        RoomsAndCorridorsLevel::generate(size);
-    let cave_map = builders::generate_cave_map(size);
-    let mut combined_map = combine_rooms_and_corridors_level_with_cave(&rooms_and_corridors_map, &cave_map);
+// Mounts a terminal on the wall for each of the logs left on this floor
+fn add_terminals(map: &mut Grid<LevelCell>, level: u8) {
+    let mut candidates =
+        map.coord_iter().filter(|&coord| wall_mount_facing(map, coord).is_some()).collect::<Vec<_>>();
+    crate::rng::shuffle(&mut candidates);
 
-    *combined_map.get_checked_mut(player_spawn) = LevelCell::PlayerSpawn;
+    for index in 0..floor_entries(level).len() {
+        if let Some(coord) = candidates.pop() {
+            *map.get_checked_mut(coord) = LevelCell::Terminal(index as u8);
+        }
+    }
+}
 
-    let mut water_map = make_water_map(size);
-    remove_unreachable_floor(&mut combined_map, &mut water_map, player_spawn);
-    remove_invalid_doors(&mut combined_map);
-    add_grass(&mut combined_map);
+// Mounts sockets on the walls where the player can recharge their flashlight
+fn add_power_sockets(map: &mut Grid<LevelCell>) {
+    let mut candidates =
+        map.coord_iter().filter(|&coord| wall_mount_facing(map, coord).is_some()).collect::<Vec<_>>();
+    crate::rng::shuffle(&mut candidates);
 
-    if level != FINAL_LEVEL {
-        choose_stairs_coord(&mut combined_map, player_spawn);
-    } else {
-        choose_reactor_coord(&mut combined_map, player_spawn);
+    for coord in candidates.into_iter().take(NUM_POWER_SOCKETS) {
+        *map.get_checked_mut(coord) = LevelCell::PowerSocket;
+    }
+}
+
+// Returns every cell the player can reach from `start` while holding a keycard of the given clearance
+pub(super) fn reachable_with_clearance(
+    map: &Grid<LevelCell>,
+    start: Coord,
+    clearance: Option<Clearance>,
+) -> Grid<bool> {
+    let mut seen = Grid::new_copy(map.size(), false);
+    *seen.get_checked_mut(start) = true;
+    let mut to_visit = vec![start];
+    while let Some(current) = to_visit.pop() {
+        for direction in CardinalDirection::all() {
+            let neighbour_coord = current + direction.coord();
+            let passable = match map.get(neighbour_coord) {
+                None => false,
+                Some(LevelCell::LockedDoor(required)) => Clearance::can_open(clearance, *required),
+                Some(cell) => !cell.is_solid(),
+            };
+            if passable && !*seen.get_checked(neighbour_coord) {
+                *seen.get_checked_mut(neighbour_coord) = true;
+                to_visit.push(neighbour_coord);
+            }
+        }
     }
+    seen
+}
 
-    for (coord, cell) in combined_map.enumerate_mut() {
-        use LevelCell::*;
+// Narrows some of the doors on the floor, shutting heavy robots out of the rooms behind them
+fn add_narrow_doors(map: &mut Grid<LevelCell>) {
+    for cell in map.iter_mut() {
+        if *cell == LevelCell::Door && crate::rng::range(0..100) < NARROW_DOOR_CHANCE {
+            *cell = LevelCell::NarrowDoor;
+        }
+    }
+}
 
-        if *water_map.get_checked(coord) {
-            match cell {
-                Grass => *cell = Water,
-                Floor | Door => *cell = Water,
-                CaveFloor | CaveWall => *cell = Water,
-                Reactor | Stairs | Water | PlayerSpawn | Weapon(..) => (),
-                Wall => {
-                    if crate::rng::range(0..=100) < 75 {
-                        *cell = Water
-                    }
-                }
-                Light(..) => {
-                    *cell = Light(Rgb24 { r: 0, g: 0, b: 200 });
+// Locks some of the doors on the floor, working up from the lowest clearance. The keycard for each
+// clearance is placed in a room the player can reach using only the keycards below it, and a door is
+// only locked if every keycard placed so far can still be reached, so every locked door can always be
+// opened.
+fn add_locked_doors(map: &mut Grid<LevelCell>, player_coord: Coord, level: u8) {
+    let mut doors = map
+        .enumerate()
+        .filter(|(_, cell)| **cell == LevelCell::Door)
+        .map(|(coord, _)| coord)
+        .collect::<Vec<_>>();
+    crate::rng::shuffle(&mut doors);
+
+    // Each keycard along with the best keycard the player can hold before reaching it
+    let mut keycards: Vec<(Coord, Option<Clearance>)> = Vec::new();
+    let mut clearance_below = None;
+    for clearance in Clearance::for_level(level) {
+        let mut locked = Vec::new();
+        while locked.len() < LOCKED_DOORS_PER_CLEARANCE {
+            let coord = if let Some(coord) = doors.pop() { coord } else { break };
+            *map.get_checked_mut(coord) = LevelCell::LockedDoor(clearance);
+            let keycards_reachable = keycards.iter().all(|&(keycard_coord, held)| {
+                *reachable_with_clearance(map, player_coord, held).get_checked(keycard_coord)
+            });
+            if keycards_reachable {
+                locked.push(coord);
+            } else {
+                *map.get_checked_mut(coord) = LevelCell::Door;
+            }
+        }
+
+        if locked.is_empty() {
+            continue;
+        }
+
+        let reachable = reachable_with_clearance(map, player_coord, clearance_below);
+        let mut keycard_coords = map
+            .enumerate()
+            .filter(|(coord, cell)| {
+                **cell == LevelCell::Floor && *reachable.get_checked(*coord) && *coord != player_coord
+            })
+            .map(|(coord, _)| coord)
+            .collect::<Vec<_>>();
+        crate::rng::shuffle(&mut keycard_coords);
+
+        if let Some(coord) = keycard_coords.pop() {
+            *map.get_checked_mut(coord) = LevelCell::Keycard(clearance);
+            keycards.push((coord, clearance_below));
+            clearance_below = Some(clearance);
+        } else {
+            // Nowhere to put the keycard, so leave these doors unlocked
+            for coord in locked {
+                *map.get_checked_mut(coord) = LevelCell::Door;
+            }
+        }
+    }
+}
+
+// Stamps a vault into a copy of the map with its top-left corner at the given coord, and digs a
+// corridor out from each of its doors to the nearest open cell. Returns the new map along with every
+// cell the vault and its corridors cover, or `None` if the vault doesn't fit there. A vault never
+// cuts the player off from anywhere they could reach before, and is kept as far from where the
+// player arrives as items and robots are, since vaults bring their own.
+fn try_stamp_vault(
+    map: &Grid<LevelCell>,
+    cells: &Grid<LevelCell>,
+    top_left: Coord,
+    player_spawn: Coord,
+) -> Option<(Grid<LevelCell>, Vec<Coord>)> {
+    let mut stamped = map.clone();
+    let mut covered = Vec::new();
+    let mut doors = Vec::new();
+    for (offset, &cell) in cells.enumerate() {
+        let coord = top_left + offset;
+        if map.get(coord).is_none()
+            || coord.manhattan_distance(player_spawn) <= MIN_ITEM_DISTANCE
+            || builders::is_part_of_hidden_room(map, coord)
+        {
+            return None;
+        }
+        *stamped.get_checked_mut(coord) = cell;
+        covered.push(coord);
+        if cell == LevelCell::Door {
+            let outward = CardinalDirection::all().find(|direction| {
+                let neighbour = offset + direction.coord();
+                !neighbour.is_valid(cells.size())
+            })?;
+            doors.push((coord, outward));
+        }
+    }
+    if doors.is_empty() {
+        return None;
+    }
+
+    let size = map.size();
+    let inside_hull = |coord: Coord| {
+        coord.x > 0 && coord.y > 0 && coord.x < size.x() as i32 - 1 && coord.y < size.y() as i32 - 1
+    };
+    for &(door, outward) in &doors {
+        let mut coord = door + outward.coord();
+        let mut length = 0;
+        while stamped.get(coord).map_or(false, |cell| cell.is_wall()) {
+            if length == MAX_VAULT_CORRIDOR_LENGTH
+                || !inside_hull(coord)
+                || builders::is_part_of_hidden_room(map, coord)
+            {
+                return None;
+            }
+            *stamped.get_checked_mut(coord) = LevelCell::Floor;
+            covered.push(coord);
+            coord += outward.coord();
+            length += 1;
+        }
+    }
+
+    let all_access = Some(Clearance::Red);
+    let before = reachable_with_clearance(map, player_spawn, all_access);
+    let after = reachable_with_clearance(&stamped, player_spawn, all_access);
+    let cut_off = before
+        .enumerate()
+        .any(|(coord, &reachable)| reachable && !*after.get_checked(coord) && !covered.contains(&coord));
+    let doors_reachable = doors.iter().all(|&(door, _)| *after.get_checked(door));
+    if cut_off || !doors_reachable {
+        return None;
+    }
+
+    Some((stamped, covered))
+}
+
+// Places a few hand-authored vaults on the floor, each turned and mirrored at random
+fn add_vaults(map: &mut Grid<LevelCell>, water_map: &mut Grid<bool>, player_spawn: Coord, level: u8) {
+    let mut vaults = vaults::vaults_for_level(level);
+    crate::rng::shuffle(&mut vaults);
+
+    let num_vaults = 1 + level as usize / 3;
+    for vault in vaults.into_iter().take(num_vaults) {
+        for _ in 0..NUM_VAULT_ATTEMPTS {
+            let cells = vault.transformed(crate::rng::range(0..4), crate::rng::range(0..2) == 0);
+            let free = map.size().to_coord().unwrap() - cells.size().to_coord().unwrap();
+            if free.x < 2 || free.y < 2 {
+                break;
+            }
+            let top_left = Coord::new(crate::rng::range(1..free.x), crate::rng::range(1..free.y));
+            if let Some((stamped, covered)) = try_stamp_vault(map, &cells, top_left, player_spawn) {
+                log::info!("Placed {} vault at {:?}", vault.name, top_left);
+                *map = stamped;
+                for coord in covered {
+                    *water_map.get_checked_mut(coord) = false;
                 }
+                break;
             }
         }
     }
+}
 
-    print_map(&combined_map);
+// Vaults and authored levels are parsed without knowing which floor they're on, so the weapon table
+// doesn't scale with depth and any floor can be passed when rolling on it
+pub fn choose_random_weapon() -> LevelCell {
+    roll_loot(LootSource::Weapon, 0).map_or(LevelCell::Floor, Loot::level_cell)
+}
 
-    combined_map
+pub fn generate(size: Size, level: u8, theme: Theme) -> Result<LevelBuild, GenerationError> {
+    let build = theme.recipe(level).pipeline(theme).build(size, level)?;
+    print_map(&build.map);
+    Ok(build)
 }
 
 //////////////////////////////////////////////////////////////////////////////////////////
-/// Item generation
+/// Generation stages
 //////////////////////////////////////////////////////////////////////////////////////////
 
-pub fn generate_items(
-    level: u8,
-    world: &mut World,
-    terrain_state: &mut TerrainState,
-    empty_coords: &mut Vec<Coord>,
-) {
-    crate::rng::shuffle(empty_coords);
+/// Carves out rooms joined by L-shaped corridors, and starts the player in one of the rooms
+pub struct RoomsAndCorridors {
+    pub num_room_attempts: usize,
+    pub min_room_size: Size,
+    pub max_room_size: Size,
+}
+
+impl Default for RoomsAndCorridors {
+    fn default() -> Self {
+        Self {
+            num_room_attempts: 50,
+            min_room_size: Size::new_u16(5, 5),
+            max_room_size: Size::new_u16(11, 9),
+        }
+    }
+}
+
+impl LevelBuilder for RoomsAndCorridors {
+    fn build(&self, build: &mut LevelBuild) {
+        let RoomsAndCorridorsLevel { map, player_spawn, rooms, hidden_rooms } =
+            RoomsAndCorridorsLevel::generate(
+                build.size(),
+                self.num_room_attempts,
+                self.min_room_size,
+                self.max_room_size,
+            );
+        let no_caves = Grid::new_copy(build.size(), FloorOrWall::Wall);
+        build.map = combine_rooms_and_corridors_level_with_cave(&map, &no_caves);
+        build.player_spawn = player_spawn;
+        build.rooms = rooms;
+        build.hidden_rooms = hidden_rooms;
+    }
+}
+
+/// Packs the floor with small offices, with corridors running between blocks of them
+pub struct Offices;
+
+impl LevelBuilder for Offices {
+    fn build(&self, build: &mut LevelBuild) {
+        let OfficeLevel { map, player_spawn, offices } = OfficeLevel::generate(build.size());
+        build.map = map;
+        build.player_spawn = player_spawn;
+        build.rooms = offices;
+    }
+}
+
+/// Digs winding maintenance tunnels until the given fraction of the floor is open
+pub struct Tunnels {
+    pub floor_fraction: f64,
+}
+
+impl Default for Tunnels {
+    fn default() -> Self {
+        Self { floor_fraction: 0.35 }
+    }
+}
+
+impl LevelBuilder for Tunnels {
+    fn build(&self, build: &mut LevelBuild) {
+        let TunnelLevel { map, player_spawn } = TunnelLevel::generate(build.size(), self.floor_fraction);
+        build.map = map;
+        build.player_spawn = player_spawn;
+    }
+}
+
+/// Opens up caverns across the floor, breaking through any walls in the way other than those of
+/// hidden rooms
+pub struct Caves {
+    pub density: f64,
+}
+
+impl Default for Caves {
+    fn default() -> Self {
+        Self { density: 0.5 }
+    }
+}
+
+impl LevelBuilder for Caves {
+    fn build(&self, build: &mut LevelBuild) {
+        let cave_map = builders::generate_cave_map(build.size(), self.density);
+        let hidden = Grid::new_fn(build.size(), |coord| builders::is_part_of_hidden_room(&build.map, coord));
+        for ((cell, &cave_cell), &hidden) in build.map.iter_mut().zip(cave_map.iter()).zip(hidden.iter()) {
+            if cave_cell == FloorOrWall::Floor && !hidden {
+                *cell = LevelCell::CaveFloor;
+            }
+        }
+    }
+}
+
+/// Chooses where water will be, without flooding anything yet
+pub struct Water {
+    pub threshold: f64,
+}
+
+impl Default for Water {
+    fn default() -> Self {
+        Self { threshold: 0.65 }
+    }
+}
+
+impl LevelBuilder for Water {
+    fn build(&self, build: &mut LevelBuild) {
+        build.water = make_water_map(build.size(), self.threshold);
+    }
+}
+
+/// Places the player and removes anything they can't reach, along with doors left in odd places
+pub struct Connect;
+
+impl LevelBuilder for Connect {
+    fn build(&self, build: &mut LevelBuild) {
+        *build.map.get_checked_mut(build.player_spawn) = LevelCell::PlayerSpawn;
+        remove_unreachable_floor(&mut build.map, &mut build.water, build.player_spawn);
+        remove_invalid_doors(&mut build.map);
+    }
+}
+
+pub struct Grass {
+    pub threshold: f64,
+}
+
+impl Default for Grass {
+    fn default() -> Self {
+        Self { threshold: 0. }
+    }
+}
+
+impl LevelBuilder for Grass {
+    fn build(&self, build: &mut LevelBuild) {
+        add_grass(&mut build.map, self.threshold);
+    }
+}
+
+pub struct Vaults;
 
-    for _ in 0..2 {
-        if let Some(coord) = empty_coords.pop() {
-            world.spawn_credit(coord, 2);
+impl LevelBuilder for Vaults {
+    fn build(&self, build: &mut LevelBuild) {
+        add_vaults(&mut build.map, &mut build.water, build.player_spawn, build.level);
+    }
+}
+
+/// Places the stairs far from the player, or the reactor on the final floor. If there's nowhere
+/// far enough away the floor is left without an exit, and fails validation.
+pub struct Exit;
+
+impl LevelBuilder for Exit {
+    fn build(&self, build: &mut LevelBuild) {
+        if build.level != FINAL_LEVEL {
+            build.stairs = choose_stairs_coord(&mut build.map, build.player_spawn);
+        } else {
+            build.reactor = choose_reactor_coord(&mut build.map, build.player_spawn);
         }
     }
-    for _ in 0..4 {
-        if let Some(coord) = empty_coords.pop() {
-            world.spawn_credit(coord, 1);
+}
+
+/// Floods the cells chosen by the `Water` stage. Most walls under water are washed away, but hidden
+/// rooms are left dry and intact.
+pub struct Flood;
+
+impl LevelBuilder for Flood {
+    fn build(&self, build: &mut LevelBuild) {
+        let hidden = Grid::new_fn(build.size(), |coord| builders::is_part_of_hidden_room(&build.map, coord));
+        for (coord, cell) in build.map.enumerate_mut() {
+            use LevelCell::*;
+
+            if *build.water.get_checked(coord) && !*hidden.get_checked(coord) {
+                match cell {
+                    Grass => *cell = Water,
+                    Floor | Door => *cell = Water,
+                    CaveFloor | CaveWall => *cell = Water,
+                    Reactor | ShieldGenerator | Generator | Stairs | Water | PlayerSpawn | Camera(..)
+                    | AlarmPanel | Terminal(..) | PowerSocket | LockedDoor(..) | Keycard(..) | Weapon(..)
+                    | Medkit | Credit(..) | ServerRack | Upgrade | Npc(..) | SecretDoor | HiddenFloor
+                    | Chasm | Bridge | NarrowDoor => (),
+                    Wall => {
+                        if crate::rng::range(0..=100) < 75 {
+                            *cell = Water
+                        }
+                    }
+                    Light(..) => {
+                        *cell = Light(Rgb24 { r: 0, g: 0, b: 200 });
+                    }
+                }
+            }
         }
     }
-    for _ in 0..1 {
-        if let Some(coord) = empty_coords.pop() {
-            world.spawn_medkit(coord);
+}
+
+// Chasms don't open up this close to where the player arrives
+const CHASM_MIN_SPAWN_DISTANCE: u32 = 4;
+
+/// Opens chasms across the caverns. Rooms and corridors are left alone.
+pub struct Chasms {
+    pub threshold: f64,
+}
+
+impl Default for Chasms {
+    fn default() -> Self {
+        Self { threshold: 0.75 }
+    }
+}
+
+impl LevelBuilder for Chasms {
+    fn build(&self, build: &mut LevelBuild) {
+        let chasm_map = builders::make_chasm_map(build.size(), self.threshold);
+        for (coord, cell) in build.map.enumerate_mut() {
+            let in_cavern = matches!(cell, LevelCell::CaveFloor | LevelCell::Grass);
+            if in_cavern
+                && *chasm_map.get_checked(coord)
+                && coord.manhattan_distance(build.player_spawn) >= CHASM_MIN_SPAWN_DISTANCE
+            {
+                *cell = LevelCell::Chasm;
+            }
+        }
+    }
+}
+
+/// Bridges water and chasms wherever they cut off part of the floor from the player
+pub struct Bridges;
+
+impl LevelBuilder for Bridges {
+    fn build(&self, build: &mut LevelBuild) {
+        add_bridges(&mut build.map, build.player_spawn);
+    }
+}
+
+/// Rolls on the hidden room loot table a couple of times for each hidden room
+pub struct HiddenLoot;
+
+const HIDDEN_ROOM_LOOT_ROLLS: usize = 2;
+
+impl LevelBuilder for HiddenLoot {
+    fn build(&self, build: &mut LevelBuild) {
+        for room in &build.hidden_rooms {
+            let mut floor = room
+                .iter()
+                .cloned()
+                .filter(|&coord| *build.map.get_checked(coord) == LevelCell::HiddenFloor)
+                .collect::<Vec<_>>();
+            crate::rng::shuffle(&mut floor);
+            for _ in 0..HIDDEN_ROOM_LOOT_ROLLS {
+                if let (Some(loot), Some(coord)) =
+                    (roll_loot(LootSource::HiddenRoom, build.level), floor.pop())
+                {
+                    *build.map.get_checked_mut(coord) = loot.level_cell();
+                }
+            }
         }
     }
+}
+
+pub struct ShieldGenerators;
 
-    for _ in 0..2 {
-        if let Some(coord) = empty_coords.pop() {
-            let wpn = terrain_state.ranged_weapons.pop().unwrap();
-            world.spawn_weapon(coord, wpn);
+impl LevelBuilder for ShieldGenerators {
+    fn build(&self, build: &mut LevelBuild) {
+        if let Some(reactor_coord) = build.reactor {
+            add_shield_generators(&mut build.map, reactor_coord);
         }
     }
+}
+
+pub struct PowerGenerators;
+
+impl LevelBuilder for PowerGenerators {
+    fn build(&self, build: &mut LevelBuild) {
+        add_power_generators(&mut build.map, build.level);
+    }
+}
+
+pub struct Security;
+
+impl LevelBuilder for Security {
+    fn build(&self, build: &mut LevelBuild) {
+        add_security(&mut build.map, build.player_spawn, build.level);
+    }
+}
+
+pub struct Terminals;
+
+impl LevelBuilder for Terminals {
+    fn build(&self, build: &mut LevelBuild) {
+        add_terminals(&mut build.map, build.level);
+    }
+}
+
+pub struct PowerSockets;
+
+impl LevelBuilder for PowerSockets {
+    fn build(&self, build: &mut LevelBuild) {
+        add_power_sockets(&mut build.map);
+    }
+}
+
+pub struct NarrowDoors;
+
+impl LevelBuilder for NarrowDoors {
+    fn build(&self, build: &mut LevelBuild) {
+        add_narrow_doors(&mut build.map);
+    }
+}
+
+pub struct LockedDoors;
+
+impl LevelBuilder for LockedDoors {
+    fn build(&self, build: &mut LevelBuild) {
+        add_locked_doors(&mut build.map, build.player_spawn, build.level);
+    }
+}
+
+//////////////////////////////////////////////////////////////////////////////////////////
+/// Item generation
+//////////////////////////////////////////////////////////////////////////////////////////
+
+// Medkits are kept away from the exit, so that healing up means taking a detour
+const MEDKIT_MIN_EXIT_DISTANCE: u32 = 15;
+// Number of times each floor's loot tables are rolled
+const DEAD_END_LOOT_ROLLS: usize = 3;
+const FLOOR_LOOT_ROLLS: usize = 7;
+
+// The dead-end loot table is rolled for rooms with only one way in, and the floor loot table for
+// everywhere else. Medkits are left far from the exit.
+pub fn generate_items(
+    level: u8,
+    world: &mut World,
+    plan: &FloorPlan,
+    empty_coords: &mut Vec<Coord>,
+) -> Result<(), CellOccupied> {
+    crate::rng::shuffle(empty_coords);
 
-    if terrain_state.cattle_prod_floors.contains(&level) {
-        if let Some(coord) = empty_coords.pop() {
-            world.spawn_weapon(coord, WeaponType::CattleProd);
+    for _ in 0..DEAD_END_LOOT_ROLLS {
+        if let Some(loot) = roll_loot(LootSource::DeadEnd, level) {
+            if let Some(coord) = take_preferring(empty_coords, |&coord| plan.zone(coord) == Zone::DeadEnd) {
+                world.spawn_loot(coord, loot)?;
+            }
         }
     }
 
-    if terrain_state.chainsaw_floors.contains(&level) {
-        if let Some(coord) = empty_coords.pop() {
-            world.spawn_weapon(coord, WeaponType::Chainsaw);
+    for _ in 0..FLOOR_LOOT_ROLLS {
+        if let Some(loot) = roll_loot(LootSource::Floor, level) {
+            let coord = if loot == Loot::Medkit {
+                take_preferring(empty_coords, |&coord| {
+                    plan.distance_to_exit(coord) >= MEDKIT_MIN_EXIT_DISTANCE
+                })
+            } else {
+                empty_coords.pop()
+            };
+            if let Some(coord) = coord {
+                world.spawn_loot(coord, loot)?;
+            }
         }
     }
 
@@ -179,10 +745,11 @@ pub fn generate_items(
                 }
             }
         }
-        world.spawn_upgrade(coord);
+        world.spawn_upgrade(coord)?;
         empty_coords.swap_remove(i);
         break;
     }
+    Ok(())
 }
 
 //////////////////////////////////////////////////////////////////////////////////////////
@@ -191,57 +758,243 @@ pub fn generate_items(
 use crate::{Agent, NpcType, World};
 use gridbugs::{entity_table::ComponentTable, spatial_table::Coord};
 
-pub struct EnemyCounts {
-    mini: Vec<usize>,
-    sec: Vec<usize>,
-    sentry: Vec<usize>,
-    doom: Vec<usize>,
-}
+// Turrets are placed on the floor a few steps away from whatever they are guarding
+const TURRET_MIN_GUARD_DISTANCE: u32 = 2;
+const TURRET_MAX_GUARD_DISTANCE: u32 = 4;
 
-impl EnemyCounts {
-    fn new() -> Self {
-        Self {
-            mini: vec![8, 10, 10, 12, 12],
-            sec: vec![0, 1, 2, 5, 6],
-            sentry: vec![0, 0, 1, 2, 2],
-            doom: vec![0, 0, 0, 1, 2],
+fn place_turrets_around(
+    world: &mut World,
+    guarded_coord: Coord,
+    count: usize,
+    npc_candidates: &mut Vec<Coord>,
+    agents: &mut ComponentTable<Agent>,
+) -> Result<(), CellOccupied> {
+    for _ in 0..count {
+        let position = npc_candidates.iter().position(|coord| {
+            let distance = coord.manhattan_distance(guarded_coord);
+            (TURRET_MIN_GUARD_DISTANCE..=TURRET_MAX_GUARD_DISTANCE).contains(&distance)
+        });
+
+        if let Some(index) = position {
+            let coord = npc_candidates.swap_remove(index);
+            let turret = world.spawn_turret(coord)?;
+            agents.insert(turret, Agent::new(world.size(), NpcType::Turret));
         }
     }
+    Ok(())
 }
 
-pub fn generate_npcs(
+/// Places turrets guarding the upgrade store and the reactor
+pub fn generate_turrets(
     level: u8,
     world: &mut World,
     npc_candidates: &mut Vec<Coord>,
     agents: &mut ComponentTable<Agent>,
-) {
+) -> Result<(), CellOccupied> {
     crate::rng::shuffle(npc_candidates);
 
-    let index = level as usize - 1;
-    let enemy_count = EnemyCounts::new();
+    let upgrade_coords =
+        world.components.upgrade.entities().filter_map(|e| world.entity_coord(e)).collect::<Vec<_>>();
+    if level >= 2 {
+        for coord in upgrade_coords {
+            place_turrets_around(world, coord, 1, npc_candidates, agents)?;
+        }
+    }
+
+    let reactor_coords =
+        world.components.reactor.entities().filter_map(|e| world.entity_coord(e)).collect::<Vec<_>>();
+    for coord in reactor_coords {
+        place_turrets_around(world, coord, 2, npc_candidates, agents)?;
+    }
+    Ok(())
+}
+
+// The total threat of the robots on each floor
+const THREAT_BUDGETS: [u32; FINAL_LEVEL as usize] = [8, 19, 31, 58, 74];
 
-    for _ in 0..enemy_count.mini[index] {
-        if let Some(coord) = npc_candidates.pop() {
-            let mini = world.spawn_minibot(coord);
-            agents.insert(mini, Agent::new(world.size(), NpcType::MiniBot));
+// How much a robot costs out of a floor's threat budget, the first floor it can appear on, and how
+// likely it is to be chosen compared to the others
+struct Threat {
+    npc_type: NpcType,
+    cost: u32,
+    min_level: u8,
+    weight: u32,
+}
+
+const THREATS: &[Threat] = &[
+    Threat { npc_type: NpcType::MiniBot, cost: 1, min_level: 1, weight: 6 },
+    Threat { npc_type: NpcType::SecBot, cost: 3, min_level: 2, weight: 3 },
+    Threat { npc_type: NpcType::Drone, cost: 2, min_level: 2, weight: 3 },
+    Threat { npc_type: NpcType::RepairBot, cost: 2, min_level: 2, weight: 2 },
+    Threat { npc_type: NpcType::RoboCop, cost: 4, min_level: 3, weight: 2 },
+    Threat { npc_type: NpcType::ShieldBot, cost: 3, min_level: 3, weight: 2 },
+    Threat { npc_type: NpcType::Skimmer, cost: 2, min_level: 3, weight: 2 },
+    Threat { npc_type: NpcType::DoomBot, cost: 8, min_level: 4, weight: 1 },
+];
+
+// Spends the floor's threat budget on robots. The theme's signature robots are bought first, from
+// the second floor on, and the rest of the budget is spent at random on robots it can still afford.
+fn choose_robots(level: u8, theme: Theme) -> Vec<NpcType> {
+    let mut budget = THREAT_BUDGETS[level as usize - 1];
+    let mut robots = Vec::new();
+
+    if level >= 2 {
+        let (signature, count) = theme.signature_npc();
+        if let Some(threat) = THREATS.iter().find(|threat| threat.npc_type == signature) {
+            for _ in 0..count {
+                if threat.cost <= budget {
+                    budget -= threat.cost;
+                    robots.push(signature);
+                }
+            }
+        }
+    }
+
+    loop {
+        let affordable = THREATS
+            .iter()
+            .filter(|threat| threat.min_level <= level && threat.cost <= budget)
+            .collect::<Vec<_>>();
+        if let Some(threat) = crate::rng::choose_weighted(&affordable, |threat| threat.weight) {
+            budget -= threat.cost;
+            robots.push(threat.npc_type);
+        } else {
+            break;
+        }
+    }
+    robots
+}
+
+// Guards wait by doors, patrols start in corridors, and robots at home in the water or unable to
+// move start in rooms or out in the open. Heavy robots start only in the open, since a room may have
+// no door wide enough to let them out. Nothing is placed in dead-end rooms unless there's nowhere else.
+fn prefers_coord(npc_type: NpcType, plan: &FloorPlan, coord: Coord) -> bool {
+    match npc_type {
+        NpcType::SecBot | NpcType::RoboCop | NpcType::ShieldBot => {
+            plan.is_guard_post(coord) && plan.zone(coord) != Zone::DeadEnd
         }
+        NpcType::MiniBot | NpcType::Drone | NpcType::RepairBot => plan.zone(coord) == Zone::Corridor,
+        NpcType::DoomBot => plan.zone(coord) == Zone::Open,
+        NpcType::Skimmer | NpcType::Turret => matches!(plan.zone(coord), Zone::Open | Zone::Room),
     }
-    for _ in 0..enemy_count.sec[index] {
-        if let Some(coord) = npc_candidates.pop() {
-            let sec_bot = world.spawn_secbot(coord);
-            agents.insert(sec_bot, Agent::new(world.size(), NpcType::SecBot));
+}
+
+pub fn generate_npcs(
+    level: u8,
+    theme: Theme,
+    world: &mut World,
+    plan: &FloorPlan,
+    npc_candidates: &mut Vec<Coord>,
+    agents: &mut ComponentTable<Agent>,
+) -> Result<(), CellOccupied> {
+    crate::rng::shuffle(npc_candidates);
+
+    for npc_type in choose_robots(level, theme) {
+        if let Some(coord) = take_preferring(npc_candidates, |&coord| prefers_coord(npc_type, plan, coord)) {
+            let npc = world.spawn_npc(coord, npc_type)?;
+            agents.insert(npc, Agent::new(world.size(), npc_type));
         }
     }
-    for _ in 0..enemy_count.sentry[index] {
-        if let Some(coord) = npc_candidates.pop() {
-            let sentry = world.spawn_robocop(coord);
-            agents.insert(sentry, Agent::new(world.size(), NpcType::RoboCop));
+    Ok(())
+}
+
+//////////////////////////////////////////////////////////////////////////////////////////
+/// Objective generation
+//////////////////////////////////////////////////////////////////////////////////////////
+
+// Robots guarding the server room start within a few steps of the rack
+const SERVER_GUARD_MAX_DISTANCE: u32 = 3;
+const RELAY_REWARD: u32 = 3;
+const DOG_TAGS_REWARD: u32 = 2;
+
+// Removes and returns a coord away from any walls, so that a solid feature placed there won't cut
+// off part of the floor
+fn take_open_coord(world: &World, candidates: &mut Vec<Coord>) -> Option<Coord> {
+    let index = candidates.iter().position(|&coord| {
+        Directions.into_iter().all(|direction| {
+            world
+                .spatial_table
+                .layers_at(coord + direction.coord())
+                .map_or(true, |layers| layers.feature.is_none())
+        })
+    })?;
+    Some(candidates.swap_remove(index))
+}
+
+fn place_server_room_guards(
+    world: &mut World,
+    rack_coord: Coord,
+    count: usize,
+    npc_candidates: &mut Vec<Coord>,
+    agents: &mut ComponentTable<Agent>,
+) -> Result<Vec<Entity>, CellOccupied> {
+    let mut guards = Vec::new();
+    for _ in 0..count {
+        let position = npc_candidates.iter().position(|coord| {
+            (1..=SERVER_GUARD_MAX_DISTANCE).contains(&coord.manhattan_distance(rack_coord))
+        });
+
+        if let Some(index) = position {
+            let coord = npc_candidates.swap_remove(index);
+            let guard = world.spawn_secbot(coord)?;
+            agents.insert(guard, Agent::new(world.size(), NpcType::SecBot));
+            guards.push(guard);
         }
     }
-    for _ in 0..enemy_count.doom[index] {
-        if let Some(coord) = npc_candidates.pop() {
-            let doom = world.spawn_doombot(coord);
-            agents.insert(doom, Agent::new(world.size(), NpcType::DoomBot));
+    Ok(guards)
+}
+
+fn generate_objective(
+    typ: ObjectiveType,
+    level: u8,
+    world: &mut World,
+    empty_coords: &mut Vec<Coord>,
+    agents: &mut ComponentTable<Agent>,
+) -> Result<Option<Objective>, CellOccupied> {
+    let coord = match typ {
+        ObjectiveType::DestroyRelay | ObjectiveType::ClearServerRoom => take_open_coord(world, empty_coords),
+        ObjectiveType::RecoverDogTags => empty_coords.pop(),
+    };
+    let coord = if let Some(coord) = coord { coord } else { return Ok(None) };
+    let (reward, targets) = match typ {
+        ObjectiveType::DestroyRelay => (Reward::Credit(RELAY_REWARD), vec![world.spawn_power_relay(coord)?]),
+        ObjectiveType::RecoverDogTags => {
+            (Reward::Credit(DOG_TAGS_REWARD), vec![world.spawn_dog_tags(coord)?])
         }
+        ObjectiveType::ClearServerRoom => {
+            world.spawn_server_rack(coord)?;
+            let num_guards = 1 + level as usize / 2;
+            (Reward::Upgrade, place_server_room_guards(world, coord, num_guards, empty_coords, agents)?)
+        }
+    };
+
+    if targets.is_empty() {
+        Ok(None)
+    } else {
+        Ok(Some(Objective::new(typ, reward, targets)))
+    }
+}
+
+/// Picks a few optional objectives for the floor and places whatever they need. The final floor has
+/// no optional objectives, as the reactor is objective enough.
+pub fn generate_objectives(
+    level: u8,
+    world: &mut World,
+    empty_coords: &mut Vec<Coord>,
+    agents: &mut ComponentTable<Agent>,
+) -> Result<Vec<Objective>, CellOccupied> {
+    if level == FINAL_LEVEL {
+        return Ok(Vec::new());
     }
+
+    crate::rng::shuffle(empty_coords);
+    let mut types = ObjectiveType::ALL.to_vec();
+    crate::rng::shuffle(&mut types);
+
+    let num_objectives = if level >= 3 { 2 } else { 1 };
+    types
+        .into_iter()
+        .take(num_objectives)
+        .filter_map(|typ| generate_objective(typ, level, world, empty_coords, agents).transpose())
+        .collect()
 }
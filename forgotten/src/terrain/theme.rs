@@ -0,0 +1,141 @@
+use crate::prelude::*;
+
+/// The character of a generated floor, which decides how it's laid out, lit and scored, and
+/// which robots are most common on it
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Theme {
+    /// Security quarters, laid out as rooms and offices and patrolled by SecBots
+    Barracks,
+    /// Water storage, mostly flooded and home to Skimmers
+    Cistern,
+    /// Overgrown gardens with more cavern than station, watched over by Drones
+    Hydroponics,
+    /// The heart of the station, guarded by ShieldBots
+    ReactorCore,
+}
+
+impl Theme {
+    /// Chooses a theme for each generated floor of a run. Every theme but the reactor core appears
+    /// at least once, and the final floor is always the reactor core.
+    pub fn choose_for_run() -> Vec<Self> {
+        use Theme::*;
+        let mut themes = vec![Barracks, Cistern, Hydroponics];
+        themes.push(*crate::rng::choose(&themes).unwrap());
+        crate::rng::shuffle(&mut themes);
+        themes.push(ReactorCore);
+        themes
+    }
+
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::Barracks => "Barracks",
+            Self::Cistern => "Flooded Cistern",
+            Self::Hydroponics => "Overgrown Hydroponics",
+            Self::ReactorCore => "Reactor Core",
+        }
+    }
+
+    /// How the floor is laid out
+    pub fn recipe(self, level: u8) -> Recipe {
+        use Recipe::*;
+        // The reactor needs room around it
+        if level == FINAL_LEVEL {
+            return Station;
+        }
+        let recipes: &[Recipe] = match self {
+            Self::Barracks => &[Station, OfficeBlock],
+            Self::Cistern => &[Station, MaintenanceTunnels],
+            Self::Hydroponics => &[Station],
+            Self::ReactorCore => &[Station, MaintenanceTunnels],
+        };
+        *crate::rng::choose(recipes).unwrap()
+    }
+
+    /// The number of rooms to try placing on station floors
+    pub const fn num_room_attempts(self) -> usize {
+        match self {
+            Self::Barracks => 60,
+            Self::Cistern => 40,
+            Self::Hydroponics => 30,
+            Self::ReactorCore => 50,
+        }
+    }
+
+    /// Proportion of the floor seeded with cavern before it's grown, so higher values give more cavern
+    pub const fn cave_density(self) -> f64 {
+        match self {
+            Self::Barracks => 0.4,
+            Self::Cistern => 0.5,
+            Self::Hydroponics => 0.55,
+            Self::ReactorCore => 0.45,
+        }
+    }
+
+    /// Water spawns where noise exceeds this, so lower values flood more of the floor
+    pub const fn water_threshold(self) -> f64 {
+        match self {
+            Self::Barracks => 0.7,
+            Self::Cistern => 0.5,
+            Self::Hydroponics => 0.65,
+            Self::ReactorCore => 0.75,
+        }
+    }
+
+    /// Chasms open up in the caverns where noise exceeds this, so lower values give more chasms
+    pub const fn chasm_threshold(self) -> f64 {
+        match self {
+            Self::Barracks => 0.75,
+            Self::Cistern => 0.8,
+            Self::Hydroponics => 0.75,
+            Self::ReactorCore => 0.65,
+        }
+    }
+
+    /// Grass grows where noise exceeds this, so lower values cover more of the caverns
+    pub const fn grass_threshold(self) -> f64 {
+        match self {
+            Self::Barracks => 0.3,
+            Self::Cistern => 0.,
+            Self::Hydroponics => -0.4,
+            Self::ReactorCore => 0.5,
+        }
+    }
+
+    pub const fn ambient_light(self) -> Rgb24 {
+        match self {
+            Self::Barracks => Rgb24::new(80, 75, 70),
+            Self::Cistern => Rgb24::new(60, 70, 95),
+            Self::Hydroponics => Rgb24::new(65, 85, 60),
+            Self::ReactorCore => Rgb24::new(95, 65, 55),
+        }
+    }
+
+    pub const fn music(self) -> Music {
+        match self {
+            Self::Barracks => Music::Gameplay1,
+            Self::Cistern => Music::Gameplay2,
+            Self::Hydroponics => Music::Gameplay3,
+            Self::ReactorCore => Music::Gameplay4,
+        }
+    }
+
+    /// The robot the floor is known for, and how many of them are bought out of its threat budget
+    /// before any other robots
+    pub const fn signature_npc(self) -> (NpcType, usize) {
+        match self {
+            Self::Barracks => (NpcType::SecBot, 2),
+            Self::Cistern => (NpcType::Skimmer, 2),
+            Self::Hydroponics => (NpcType::Drone, 2),
+            Self::ReactorCore => (NpcType::ShieldBot, 1),
+        }
+    }
+
+    pub fn metadata(self) -> LevelMetadata {
+        LevelMetadata {
+            name: Some(self.name().to_string()),
+            music: Some(self.music()),
+            ambient_light: Some(self.ambient_light()),
+            theme: Some(self),
+        }
+    }
+}
@@ -0,0 +1,124 @@
+use super::{procgen::reachable_with_clearance, LevelBuild, LevelCell, MIN_ITEM_DISTANCE};
+use crate::prelude::*;
+use gridbugs::grid_2d::Grid;
+use std::fmt;
+
+/// Generated floors smaller than this are rejected
+const MIN_FLOOR_AREA: usize = 250;
+/// Items and robots need somewhere to go, away from where the player starts
+const MIN_CANDIDATE_CELLS: usize = 60;
+
+/// Why a floor couldn't be generated
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GenerationError {
+    /// Nowhere was far enough from the player for the stairs or reactor
+    NoExit,
+    ExitUnreachable {
+        exit: Coord,
+    },
+    TooLittleFloor {
+        found: usize,
+        required: usize,
+    },
+    /// An item was placed on dry land which can only be reached through water
+    StrandedItem {
+        coord: Coord,
+    },
+    TooFewCandidateCells {
+        found: usize,
+        required: usize,
+    },
+    /// Two things were placed in the same cell
+    Occupied(CellOccupied),
+    /// Every attempt at generating the floor was rejected. Holds the last reason.
+    Exhausted {
+        attempts: usize,
+        last: Box<GenerationError>,
+    },
+}
+
+impl fmt::Display for GenerationError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::NoExit => write!(f, "nowhere to put the exit"),
+            Self::ExitUnreachable { exit } => write!(f, "the exit at {:?} can't be reached", exit),
+            Self::TooLittleFloor { found, required } => {
+                write!(f, "only {} reachable cells but at least {} are needed", found, required)
+            }
+            Self::StrandedItem { coord } => {
+                write!(f, "the item at {:?} can only be reached through water", coord)
+            }
+            Self::TooFewCandidateCells { found, required } => {
+                write!(f, "only {} cells for items and robots but at least {} are needed", found, required)
+            }
+            Self::Occupied(occupied) => write!(f, "{}", occupied),
+            Self::Exhausted { attempts, last } => write!(f, "gave up after {} attempts: {}", attempts, last),
+        }
+    }
+}
+
+impl std::error::Error for GenerationError {}
+
+impl From<CellOccupied> for GenerationError {
+    fn from(occupied: CellOccupied) -> Self {
+        Self::Occupied(occupied)
+    }
+}
+
+/// Cells which can be reached from the player's spawn without wading through water. Nothing is
+/// placed anywhere else, so that no item is stranded on an island.
+pub fn dry_reachable(map: &Grid<LevelCell>) -> Grid<bool> {
+    let spawn = map.enumerate().find(|&(_, &cell)| cell == LevelCell::PlayerSpawn).map(|(coord, _)| coord);
+    let spawn = if let Some(spawn) = spawn { spawn } else { return Grid::new_copy(map.size(), false) };
+    let dry_map = Grid::new_fn(map.size(), |coord| match *map.get_checked(coord) {
+        LevelCell::Water => LevelCell::Wall,
+        cell => cell,
+    });
+    reachable_with_clearance(&dry_map, spawn, Some(Clearance::Red))
+}
+
+impl LevelBuild {
+    /// Checks that the floor can be played through: the exit can be reached, there's enough room
+    /// to move around, and items and robots have somewhere sensible to go
+    pub fn validate(&self) -> Result<(), GenerationError> {
+        let exit = self.exit().ok_or(GenerationError::NoExit)?;
+
+        // Keycards are always reachable, so any door can be opened eventually
+        let reachable = reachable_with_clearance(&self.map, self.player_spawn, Some(Clearance::Red));
+        let exit_reachable = *reachable.get_checked(exit)
+            || CardinalDirection::all()
+                .any(|direction| reachable.get(exit + direction.coord()) == Some(&true));
+        if !exit_reachable {
+            return Err(GenerationError::ExitUnreachable { exit });
+        }
+
+        let found = reachable.iter().filter(|&&reachable| reachable).count();
+        if found < MIN_FLOOR_AREA {
+            return Err(GenerationError::TooLittleFloor { found, required: MIN_FLOOR_AREA });
+        }
+
+        let dry = dry_reachable(&self.map);
+        for (coord, cell) in self.map.enumerate() {
+            use LevelCell::*;
+            let item = matches!(cell, Weapon(..) | Medkit | Credit(..) | Keycard(..));
+            if item && !*dry.get_checked(coord) {
+                return Err(GenerationError::StrandedItem { coord });
+            }
+        }
+
+        let found = self
+            .map
+            .enumerate()
+            .filter(|&(coord, cell)| {
+                cell.is_floor()
+                    && *dry.get_checked(coord)
+                    && coord.manhattan_distance(self.player_spawn) > MIN_ITEM_DISTANCE
+            })
+            .count();
+        if found < MIN_CANDIDATE_CELLS {
+            return Err(GenerationError::TooFewCandidateCells { found, required: MIN_CANDIDATE_CELLS });
+        }
+
+        Ok(())
+    }
+}
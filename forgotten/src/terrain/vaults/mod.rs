@@ -0,0 +1,57 @@
+use super::{generate_from_str, LevelCell};
+use crate::prelude::*;
+use gridbugs::grid_2d::Grid;
+
+// Hand-authored rooms which are stamped into generated floors. They use the same legend as other
+// authored levels, and any door on the edge of a vault gets connected to the rest of the floor.
+// Vaults are rotated and mirrored when placed, so nothing in them should depend on which way they face.
+const VAULTS: [(&str, u8, &str); 4] = [
+    ("armoury", 1, include_str!("armoury.txt")),
+    ("medbay", 1, include_str!("medbay.txt")),
+    ("server room", 1, include_str!("server_room.txt")),
+    ("guarded cache", 2, include_str!("guarded_cache.txt")),
+];
+
+pub struct Vault {
+    pub name: &'static str,
+    pub cells: Grid<LevelCell>,
+}
+
+/// The vaults which can appear on the given floor. Weapons are rolled afresh on each call.
+pub fn vaults_for_level(level: u8) -> Vec<Vault> {
+    VAULTS
+        .iter()
+        .filter(|&&(_, min_level, _)| level >= min_level)
+        .map(|&(name, _, text)| {
+            let cells =
+                generate_from_str(text).unwrap_or_else(|err| panic!("bad {} vault: {}", name, err)).map;
+            Vault { name, cells }
+        })
+        .collect()
+}
+
+fn rotate_clockwise(cells: &Grid<LevelCell>) -> Grid<LevelCell> {
+    let height = cells.size().height() as i32;
+    Grid::new_fn(cells.size().transpose(), |coord| {
+        *cells.get_checked(Coord::new(coord.y, height - 1 - coord.x))
+    })
+}
+
+fn mirror(cells: &Grid<LevelCell>) -> Grid<LevelCell> {
+    let width = cells.size().width() as i32;
+    Grid::new_fn(cells.size(), |coord| *cells.get_checked(Coord::new(width - 1 - coord.x, coord.y)))
+}
+
+impl Vault {
+    /// The vault's cells turned a number of quarter turns clockwise, and then optionally mirrored
+    pub fn transformed(&self, quarter_turns: u8, mirrored: bool) -> Grid<LevelCell> {
+        let mut cells = self.cells.clone();
+        for _ in 0..quarter_turns % 4 {
+            cells = rotate_clockwise(&cells);
+        }
+        if mirrored {
+            cells = mirror(&cells);
+        }
+        cells
+    }
+}
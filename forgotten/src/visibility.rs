@@ -2,18 +2,26 @@ use crate::prelude::*;
 use gridbugs::{spatial_table::Coord, visible_area_detection::CellVisibility};
 
 pub const AMBIENT_COL: Rgb24 = Rgb24::new_grey(75);
+/// What's left of the ambient light in a wing whose generator has been destroyed
+pub const BLACKOUT_COL: Rgb24 = Rgb24::new_grey(15);
 
 impl Game {
+    /// Light in cells that no lamp reaches, which authored levels can override
+    pub fn ambient_light(&self) -> Rgb24 {
+        self.level_metadata.ambient_light.unwrap_or(AMBIENT_COL)
+    }
+
     pub fn update_visibility(&mut self) {
+        let ambient_light = self.ambient_light();
         let update_fn = |data: &mut VisibleCellData, coord| data.update(&self.world, coord);
 
         if self.config.omniscient {
-            self.visibility_grid.update_omniscient_custom(AMBIENT_COL, &self.world, update_fn);
+            self.visibility_grid.update_omniscient_custom(ambient_light, &self.world, update_fn);
         } else {
             let player_vision = *self.world.components.vision.get(self.player_entity).unwrap();
             let player_coord = self.player_coord();
             self.visibility_grid.update_custom(
-                AMBIENT_COL,
+                ambient_light,
                 &self.world,
                 player_vision,
                 player_coord,
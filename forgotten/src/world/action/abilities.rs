@@ -1,5 +1,8 @@
 use crate::prelude::*;
 
+const REPAIR_AMOUNT: u32 = 2;
+const HACK_TURNS: u32 = 15;
+
 impl World {
     pub fn apply_stun(&mut self, entity: Entity, stun_percentage: u8) -> bool {
         if crate::rng::range(0..100) < stun_percentage {
@@ -10,6 +13,24 @@ impl World {
         }
     }
 
+    pub fn repair_character(&mut self, repairer: Entity, target: Entity) {
+        if !self.entity_exists(repairer) || !self.entity_exists(target) {
+            return;
+        }
+
+        if let Some(hit_points) = self.components.hp.get_mut(target) {
+            hit_points.current = (hit_points.current + REPAIR_AMOUNT).min(hit_points.max);
+        }
+
+        if let Some(npc) = self.components.npc.get(target) {
+            crate::log::append_entry(Message::EnemyRepaired(npc.npc_type));
+        }
+
+        if let Some(coord) = self.entity_coord(target) {
+            self.spawn_flash(coord, Some(Rgb24::new(0, 255, 127)));
+        }
+    }
+
     pub fn reduce_stun(&mut self, entity: Entity) {
         if let Some(stun) = self.components.stunned.get_mut(entity) {
             stun.turns -= 1;
@@ -19,4 +40,44 @@ impl World {
             }
         }
     }
+
+    /// Returns true iff the entity is a stunned robot which the player can hack
+    pub fn can_hack(&self, entity: Entity) -> bool {
+        self.components.stunned.contains(entity)
+            && self.components.npc.get(entity).map_or(false, |npc| npc.disposition != Disposition::Allied)
+    }
+
+    /// Hacked robots fight on the player's side until the hack wears off
+    pub fn hack_robot(&mut self, entity: Entity) {
+        let npc = if let Some(npc) = self.components.npc.get_mut(entity) { npc } else { return };
+        crate::log::append_entry(Message::EnemyHacked(npc.npc_type));
+        crate::event::add_event(ExternalEvent::SoundEffect(SoundEffect::CattleProd));
+
+        let hacked = Hacked { turns: HACK_TURNS, disposition: npc.disposition };
+        npc.disposition = Disposition::Allied;
+        npc.move_to = None;
+        let npc_type = npc.npc_type;
+
+        self.components.stunned.remove(entity);
+        self.components.hacked.insert(entity, hacked);
+        self.components.tile.insert(entity, Tile::AlliedNpc(npc_type));
+    }
+
+    pub fn reduce_hack(&mut self, entity: Entity) {
+        if let Some(hacked) = self.components.hacked.get_mut(entity) {
+            hacked.turns -= 1;
+
+            if hacked.turns == 0 {
+                let disposition = hacked.disposition;
+                self.components.hacked.remove(entity);
+
+                if let Some(npc) = self.components.npc.get_mut(entity) {
+                    crate::log::append_entry(Message::HackWoreOff(npc.npc_type));
+                    npc.disposition = disposition;
+                    let npc_type = npc.npc_type;
+                    self.components.tile.insert(entity, Tile::Npc(npc_type));
+                }
+            }
+        }
+    }
 }
@@ -21,49 +21,22 @@ impl World {
         );
 
         let target_coord = current_coord + direction.coord();
+        if self.is_chasm_at_coord(target_coord) && !self.entity_movement_profile(character).crosses_chasms() {
+            return ActionError::chasm();
+        }
+
         if let Some(&Layers { feature: Some(feature_entity), .. }) =
             self.spatial_table.layers_at(target_coord)
         {
-            // If the player bumps into a door, open the door
-            if let Some(DoorState::Closed) = self.components.door_state.get(feature_entity) {
-                self.open_door(feature_entity);
-
-                if self.components.player.contains(character) {
-                    crate::event::add_event(ExternalEvent::SoundEffect(SoundEffect::DoorOpen));
-                }
-
-                return Ok(None);
-            }
-
-            // Don't let the player walk through solid entities
+            // Walking into a solid feature interacts with it, such as opening a door
             if self.components.solid.contains(feature_entity) {
-                if let Some(open_door_entity) = self.open_door_entity_adjacent_to_coord(target_coord) {
-                    self.close_door(open_door_entity);
-
-                    if self.components.player.contains(character) {
-                        crate::event::add_event(ExternalEvent::SoundEffect(SoundEffect::DoorClose));
-                    }
-
-                    return Ok(None);
-                }
-
-                if self.components.upgrade.contains(feature_entity)
-                    && self.components.player.contains(character)
-                {
-                    return Ok(Some(ControlFlow::Upgrade));
-                }
-
-                // if self.components.reactor.contains(feature_entity)
-                //     && self.components.player.contains(character)
-                // {
-                //     self.damage_character(feature_entity, 1);
-                // }
-
-                return ActionError::err_cant_walk_there();
+                return self.bump_feature(character, feature_entity);
             }
 
             if let Some(GrassState::Normal) = self.components.grass_state.get(feature_entity) {
-                self.crush_grass(feature_entity);
+                if self.entity_movement_profile(character).crushes_grass() {
+                    self.crush_grass(feature_entity);
+                }
             }
         }
 
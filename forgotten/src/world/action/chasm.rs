@@ -0,0 +1,23 @@
+use crate::prelude::*;
+
+impl World {
+    /// Called when a character being knocked back or blown away reaches the edge of a chasm it
+    /// can't fly over. Robots go over the edge and are destroyed outright, while the player catches
+    /// themselves at the edge.
+    pub fn push_over_chasm(&mut self, character: Entity, chasm_coord: Coord) {
+        if self.components.player.contains(character) {
+            self.projectile_stop(character);
+            return;
+        }
+
+        if self.spatial_table.update_coord(character, chasm_coord).is_err() {
+            self.projectile_stop(character);
+            return;
+        }
+        if let Some(npc) = self.components.npc.get(character) {
+            crate::log::append_entry(Message::EnemyFellIntoChasm(npc.npc_type));
+        }
+        self.projectile_stop(character);
+        self.damage_character(character, u32::MAX);
+    }
+}
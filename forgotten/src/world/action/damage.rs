@@ -4,13 +4,20 @@ use crate::{
 };
 
 const KNOCKBACK: usize = 3;
+const KEYCARD_DROP_CHANCE: u8 = 50;
 
 impl World {
     pub fn melee_attack(&mut self, attacker: Entity, victim: Entity, direction: CardinalDirection) {
         if self.components.player.get(attacker).is_some() {
-            self.player_melee_attack(attacker, victim, direction);
+            if !self.is_allied(victim) {
+                self.player_melee_attack(attacker, victim, direction);
+            }
         } else if self.components.player.get(victim).is_some() {
-            self.npc_melee_attack(attacker, victim);
+            if !self.is_allied(attacker) {
+                self.npc_melee_attack(attacker, victim);
+            }
+        } else if self.are_robots_hostile(attacker, victim) {
+            self.npc_melee_attack_npc(attacker, victim);
         }
     }
 
@@ -37,7 +44,14 @@ impl World {
         }
 
         let pen = player.melee_pen();
-        if pen >= self.components.armour.get(victim).expect("npc lacks armour").value {
+        let armour = self.components.armour.get(victim).expect("npc lacks armour").value;
+        let effective_armour = self.effective_armour(victim).unwrap_or(armour);
+        if pen >= armour && pen < effective_armour {
+            self.log_shielded(victim);
+        }
+
+        let player = self.components.player.get(attacker).unwrap();
+        if pen >= effective_armour {
             let mut dmg = player.melee_dmg();
             if player.traits.double_damage {
                 dmg *= 2;
@@ -50,7 +64,7 @@ impl World {
         for ability in player.melee_weapon.abilities.clone() {
             use WeaponAbility::*;
             match ability {
-                KnockBack => {
+                KnockBack if !self.is_stationary(victim) => {
                     self.components.pushed_from.insert(victim, self.spatial_table.coord_of(victim).unwrap());
                     self.components.realtime.insert(victim, ());
                     self.realtime_components.movement.insert(
@@ -64,7 +78,9 @@ impl World {
                     );
                 }
                 Shock => {
-                    if self.apply_stun(victim, stun) {
+                    if self.is_stationary(victim) {
+                        self.disable_turret(victim);
+                    } else if self.apply_stun(victim, stun) {
                         if let Some(npc) = self.components.npc.get(victim) {
                             crate::log::append_entry(Message::EnemyStunned(npc.npc_type));
                         }
@@ -94,6 +110,11 @@ impl World {
             NpcType::SecBot => 20,
             NpcType::RoboCop => 25,
             NpcType::DoomBot => 40,
+            NpcType::Turret => 0,
+            NpcType::RepairBot => 5,
+            NpcType::ShieldBot => 15,
+            NpcType::Drone => 5,
+            NpcType::Skimmer => 20,
         };
 
         if self.apply_stun(victim, stun_percentage) {
@@ -104,12 +125,38 @@ impl World {
         self.damage_character(victim, damage);
     }
 
+    fn log_shielded(&self, entity: Entity) {
+        if let Some(npc) = self.components.npc.get(entity) {
+            crate::log::append_entry(Message::EnemyShielded(npc.npc_type));
+        }
+    }
+
+    pub fn npc_melee_attack_npc(&mut self, attacker: Entity, victim: Entity) {
+        let &damage = self.components.damage.get(attacker).expect("npc lacks damage component");
+        let attacker_type = self.components.npc.get(attacker).expect("npc lacks npc component").npc_type;
+        let victim_type = self.components.npc.get(victim).expect("npc lacks npc component").npc_type;
+
+        crate::event::add_event(ExternalEvent::SoundEffect(SoundEffect::Punch));
+        if self.is_allied(attacker) {
+            crate::log::append_entry(Message::AllyHitEnemy { ally: attacker_type, enemy: victim_type });
+        } else {
+            crate::log::append_entry(Message::EnemyHitAlly { enemy: attacker_type, ally: victim_type });
+        }
+
+        self.damage_character(victim, damage);
+    }
+
     pub fn damage_character(&mut self, character: Entity, hit_points_to_lose: u32) {
         if self.components.dead.contains(character) {
             // prevent cascading damage on explosions
             return;
         }
 
+        if self.components.reactor.contains(character) && self.is_reactor_shielded() {
+            crate::log::append_entry(Message::ReactorShielded);
+            return;
+        }
+
         let hit_points = self.components.hp.get_mut(character).expect("character lacks hit_points");
         if hit_points_to_lose >= hit_points.current {
             hit_points.current = 0;
@@ -125,23 +172,49 @@ impl World {
             crate::log::append_entry(Message::PlayerDies);
         } else if let Some(enemy) = self.components.npc.get(character) {
             crate::log::append_entry(Message::EnemyDies(enemy.npc_type));
+        } else if self.components.camera.contains(character) {
+            crate::log::append_entry(Message::CameraDestroyed);
+        } else if self.components.alarm_panel.contains(character) {
+            crate::log::append_entry(Message::AlarmPanelDestroyed);
+        } else if self.components.shield_generator.contains(character) {
+            crate::log::append_entry(Message::ShieldGeneratorDestroyed);
+        } else if self.components.power_relay.contains(character) {
+            crate::log::append_entry(Message::PowerRelayDestroyed);
+        } else if self.components.generator.contains(character) {
+            crate::log::append_entry(Message::GeneratorDestroyed);
+        } else if self.components.reactor.contains(character) {
+            crate::log::append_entry(Message::ReactorDestroyed);
+        }
+
+        if self.is_security_feature(character) {
+            self.wreck_security_feature(character);
+            return;
         }
 
         self.components.dead.insert(character, ());
 
+        if self.components.generator.contains(character) {
+            self.power_down(character);
+        }
+
         if let Some(npc) = self.components.npc.get(character) {
-            let random_drop = crate::rng::range(0..=100) < npc.drop_chance;
-            if random_drop {
-                let coord = self.spatial_table.coord_of(character).unwrap();
-                self.spawn_credit(
-                    coord,
-                    match npc.npc_type {
-                        NpcType::MiniBot => 1,
-                        NpcType::SecBot => 1,
-                        NpcType::RoboCop => 2,
-                        NpcType::DoomBot => 3,
-                    },
-                );
+            let coord = self.spatial_table.coord_of(character).unwrap();
+
+            // RoboCops carry the keycards for the doors they patrol
+            let keycard = (npc.npc_type == NpcType::RoboCop
+                && crate::rng::range(0..100) < KEYCARD_DROP_CHANCE)
+                .then(|| npc.npc_type.clearance())
+                .flatten();
+            let drop = roll_loot(LootSource::Drop(npc.npc_type), self.level);
+
+            // Anything dropped over a chasm would fall in with the robot, and nothing is dropped on
+            // top of an item which is already lying there
+            if !self.is_chasm_at_coord(coord) {
+                let _ = match (keycard, drop) {
+                    (Some(clearance), _) => self.spawn_keycard(coord, clearance),
+                    (None, Some(loot)) => self.spawn_loot(coord, loot),
+                    (None, None) => Ok(()),
+                };
             }
         }
 
@@ -176,8 +249,13 @@ impl World {
         projectile_movement_direction: Direction,
         entity_to_damage: Entity,
     ) {
-        if let Some(armour) = self.components.armour.get(entity_to_damage).cloned() {
-            if let Some(remaining_pen) = projectile_damage.pen.checked_sub(armour.value) {
+        if let Some(armour) = self.effective_armour(entity_to_damage) {
+            let shield_bonus = self.shield_bonus(entity_to_damage);
+            if projectile_damage.pen < armour && projectile_damage.pen >= armour - shield_bonus {
+                self.log_shielded(entity_to_damage);
+            }
+
+            if let Some(remaining_pen) = projectile_damage.pen.checked_sub(armour) {
                 if let Some(enemy) = self.components.npc.get(entity_to_damage) {
                     if let Some(weapon) = projectile_damage.weapon_name {
                         crate::log::append_entry(Message::PlayerHitEnemy { enemy: enemy.npc_type, weapon })
@@ -199,7 +277,7 @@ impl World {
                     }
                 }
                 // Push them back!
-                if projectile_damage.push_back {
+                if projectile_damage.push_back && !self.is_stationary(entity_to_damage) {
                     self.components.realtime.insert(entity_to_damage, ());
                     self.realtime_components.movement.insert(
                         entity_to_damage,
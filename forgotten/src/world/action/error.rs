@@ -1,4 +1,4 @@
-use crate::{RangedWeaponSlot, WeaponType};
+use crate::{Clearance, RangedWeaponSlot, WeaponType};
 
 pub enum ActionError {
     Message(String),
@@ -18,6 +18,34 @@ impl ActionError {
         Self::err_msg("You can't walk there!")
     }
 
+    pub fn chasm<T>() -> Result<T, Self> {
+        Self::err_msg("You'd fall into the chasm!")
+    }
+
+    pub fn door_locked<T>(clearance: Clearance) -> Result<T, Self> {
+        Self::err_msg(&format!("This door needs a {} keycard!", clearance))
+    }
+
+    pub fn door_blocked<T>() -> Result<T, Self> {
+        Self::err_msg("Something is blocking the door!")
+    }
+
+    pub fn door_jammed<T>() -> Result<T, Self> {
+        Self::err_msg("The door has no power. It's jammed open!")
+    }
+
+    pub fn flashlight_flat<T>() -> Result<T, Self> {
+        Self::err_msg("Your flashlight's battery is flat! Find a power socket to recharge it.")
+    }
+
+    pub fn socket_dead<T>() -> Result<T, Self> {
+        Self::err_msg("The socket has no power!")
+    }
+
+    pub fn nothing_to_interact_with<T>() -> Result<T, Self> {
+        Self::err_msg("There is nothing to interact with there!")
+    }
+
     pub fn no_item_there<T>() -> Result<T, Self> {
         Self::err_msg("There is no item here!")
     }
@@ -0,0 +1,90 @@
+use crate::prelude::*;
+use gridbugs::visible_area_detection::{vision_distance, Light, Rational, World as _};
+
+// How far the beam reaches before it fades out
+const FLASHLIGHT_RANGE: i32 = 8;
+const FLASHLIGHT_COLOUR: Rgb24 = Rgb24::new(255, 244, 214);
+/// Robots notice a player with their flashlight on from this far away
+pub const FLASHLIGHT_SPOTTED_DISTANCE_SQUARED: u32 = 120;
+
+impl World {
+    pub fn flashlight(&self, entity: Entity) -> Option<&Flashlight> {
+        self.components.player.get(entity).map(|player| &player.flashlight)
+    }
+
+    pub fn is_flashlight_on(&self, entity: Entity) -> bool {
+        self.flashlight(entity).map_or(false, |flashlight| flashlight.on)
+    }
+
+    pub fn toggle_flashlight(&mut self, entity: Entity) -> Result<(), ActionError> {
+        let flashlight = &mut self.components.player.get_mut(entity).expect("no player").flashlight;
+        if !flashlight.on && flashlight.battery == 0 {
+            return ActionError::flashlight_flat();
+        }
+
+        flashlight.on = !flashlight.on;
+        Ok(())
+    }
+
+    /// Points the flashlight in the direction the player moved or aimed
+    pub fn face(&mut self, entity: Entity, direction: CardinalDirection) {
+        if let Some(player) = self.components.player.get_mut(entity) {
+            player.flashlight.facing = direction;
+        }
+    }
+
+    /// Uses up a turn's worth of battery, switching the flashlight off when it runs flat
+    pub fn drain_flashlight(&mut self, entity: Entity) {
+        if let Some(player) = self.components.player.get_mut(entity) {
+            let flashlight = &mut player.flashlight;
+            if flashlight.on {
+                flashlight.battery = flashlight.battery.saturating_sub(1);
+                if flashlight.battery == 0 {
+                    flashlight.on = false;
+                    crate::log::append_entry(Message::FlashlightFlat);
+                }
+            }
+        }
+    }
+
+    pub fn recharge_flashlight(&mut self, entity: Entity) {
+        if let Some(player) = self.components.player.get_mut(entity) {
+            player.flashlight.battery = FLASHLIGHT_BATTERY;
+            crate::log::append_entry(Message::FlashlightRecharged);
+        }
+    }
+
+    /// Returns the lights making up the beams of any flashlights which are on. The beam is a line of
+    /// lights along the direction the flashlight faces, each one wider than the last so that
+    /// together they light a cone. The beam stops at the first wall it reaches.
+    pub fn flashlight_beams(&self) -> Vec<(Coord, Light<vision_distance::Circle>)> {
+        let mut beams = Vec::new();
+        for (entity, player) in self.components.player.iter() {
+            let flashlight = &player.flashlight;
+            let coord = match self.entity_coord(entity) {
+                Some(coord) if flashlight.on => coord,
+                _ => continue,
+            };
+
+            for step in 1..=FLASHLIGHT_RANGE {
+                let beam_coord = coord + flashlight.facing.coord() * step;
+                if !self.spatial_table.grid_size().is_valid(beam_coord) || self.get_opacity(beam_coord) == 255
+                {
+                    break;
+                }
+
+                if step % 2 == 1 {
+                    beams.push((
+                        beam_coord,
+                        Light {
+                            colour: FLASHLIGHT_COLOUR,
+                            vision_distance: vision_distance::Circle::new_squared(step as u32 + 1),
+                            diminish: Rational { numerator: 1, denominator: 4 },
+                        },
+                    ));
+                }
+            }
+        }
+        beams
+    }
+}
@@ -1,44 +1,283 @@
 use super::*;
+use crate::ControlFlow;
+
+/// Something a character can do to the feature or robot in front of it, such as opening a door. The
+/// player triggers an interaction explicitly with the interact action, and walking into a solid
+/// feature triggers the interactions which allow it.
+pub trait Interaction {
+    /// Returns true iff this interaction handles the given feature
+    fn applies_to(&self, world: &World, feature: Entity) -> bool;
+
+    /// Returns true iff walking into the feature should also trigger this interaction
+    fn on_bump(&self, _world: &World, _feature: Entity) -> bool {
+        true
+    }
+
+    fn interact(
+        &self,
+        world: &mut World,
+        character: Entity,
+        feature: Entity,
+    ) -> Result<Option<ControlFlow>, ActionError>;
+}
+
+/// Every interaction in the game, in the order they are checked
+const INTERACTIONS: [&dyn Interaction; 6] = [
+    &HackInteraction,
+    &DoorInteraction,
+    &UpgradeStoreInteraction,
+    &TerminalInteraction,
+    &SocketInteraction,
+    &SmashInteraction,
+];
+
+/// Turns a stunned robot to the player's side for a while
+struct HackInteraction;
+
+impl Interaction for HackInteraction {
+    fn applies_to(&self, world: &World, robot: Entity) -> bool {
+        world.can_hack(robot)
+    }
+
+    // Walking into a robot attacks it
+    fn on_bump(&self, _world: &World, _robot: Entity) -> bool {
+        false
+    }
+
+    fn interact(
+        &self,
+        world: &mut World,
+        character: Entity,
+        robot: Entity,
+    ) -> Result<Option<ControlFlow>, ActionError> {
+        if !world.components.player.contains(character) {
+            return ActionError::nothing_to_interact_with();
+        }
+        world.hack_robot(robot);
+        Ok(None)
+    }
+}
+
+/// Opens closed doors, and closes open ones
+struct DoorInteraction;
+
+impl Interaction for DoorInteraction {
+    fn applies_to(&self, world: &World, feature: Entity) -> bool {
+        world.components.door_state.contains(feature)
+    }
+
+    // Doors are only ever closed on purpose
+    fn on_bump(&self, world: &World, feature: Entity) -> bool {
+        matches!(world.components.door_state.get(feature), Some(DoorState::Closed))
+    }
+
+    fn interact(
+        &self,
+        world: &mut World,
+        character: Entity,
+        feature: Entity,
+    ) -> Result<Option<ControlFlow>, ActionError> {
+        let is_player = world.components.player.contains(character);
+        match world.components.door_state.get(feature) {
+            Some(DoorState::Closed) => {
+                if !world.can_open_door(feature, world.entity_clearance(character)) {
+                    return match world.components.locked.get(feature) {
+                        Some(&required) if is_player => ActionError::door_locked(required),
+                        _ => ActionError::err_cant_walk_there(),
+                    };
+                }
+
+                world.open_door(feature);
+                if is_player {
+                    crate::event::add_event(ExternalEvent::SoundEffect(SoundEffect::DoorOpen));
+                }
+            }
+            Some(DoorState::Open) => {
+                if !world.is_powered(feature) {
+                    return ActionError::door_jammed();
+                }
+
+                let door_coord = world.entity_coord(feature).expect("door has no coord");
+                let layers = world.spatial_table.layers_at_checked(door_coord);
+                if layers.character.is_some() || layers.item.is_some() {
+                    return ActionError::door_blocked();
+                }
+
+                world.close_door(feature);
+                if is_player {
+                    crate::event::add_event(ExternalEvent::SoundEffect(SoundEffect::DoorClose));
+                }
+            }
+            None => return ActionError::nothing_to_interact_with(),
+        }
+        Ok(None)
+    }
+}
+
+/// Opens the upgrade menu
+struct UpgradeStoreInteraction;
+
+impl Interaction for UpgradeStoreInteraction {
+    fn applies_to(&self, world: &World, feature: Entity) -> bool {
+        world.components.upgrade.contains(feature)
+    }
+
+    fn interact(
+        &self,
+        world: &mut World,
+        character: Entity,
+        _feature: Entity,
+    ) -> Result<Option<ControlFlow>, ActionError> {
+        if !world.components.player.contains(character) {
+            return ActionError::err_cant_walk_there();
+        }
+        Ok(Some(ControlFlow::Upgrade))
+    }
+}
+
+/// Reads the log stored on a terminal, adding it to the player's codex
+struct TerminalInteraction;
+
+impl Interaction for TerminalInteraction {
+    fn applies_to(&self, world: &World, feature: Entity) -> bool {
+        world.components.terminal.contains(feature)
+    }
+
+    fn interact(
+        &self,
+        world: &mut World,
+        character: Entity,
+        feature: Entity,
+    ) -> Result<Option<ControlFlow>, ActionError> {
+        let id = *world.components.terminal.get(feature).expect("terminal has no log");
+        let player = match world.components.player.get_mut(character) {
+            Some(player) => player,
+            None => return ActionError::err_cant_walk_there(),
+        };
+        if !player.codex.contains(&id) {
+            player.codex.push(id);
+        }
+        Ok(Some(ControlFlow::ReadLore(id)))
+    }
+}
+
+/// Recharges the player's flashlight, as long as the socket still has power
+struct SocketInteraction;
+
+impl Interaction for SocketInteraction {
+    fn applies_to(&self, world: &World, feature: Entity) -> bool {
+        world.components.power_socket.contains(feature)
+    }
+
+    fn interact(
+        &self,
+        world: &mut World,
+        character: Entity,
+        feature: Entity,
+    ) -> Result<Option<ControlFlow>, ActionError> {
+        if !world.components.player.contains(character) {
+            return ActionError::err_cant_walk_there();
+        }
+        if !world.is_powered(feature) {
+            return ActionError::socket_dead();
+        }
+        world.recharge_flashlight(character);
+        Ok(None)
+    }
+}
+
+/// Attacks security features, shield generators and the reactor itself
+struct SmashInteraction;
+
+impl Interaction for SmashInteraction {
+    fn applies_to(&self, world: &World, feature: Entity) -> bool {
+        world.is_smashable_feature(feature)
+    }
+
+    fn interact(
+        &self,
+        world: &mut World,
+        character: Entity,
+        feature: Entity,
+    ) -> Result<Option<ControlFlow>, ActionError> {
+        if !world.components.player.contains(character) {
+            return ActionError::err_cant_walk_there();
+        }
+        world.player_smash_feature(character, feature);
+        Ok(None)
+    }
+}
+
+impl World {
+    /// Interacts with the feature next to the character in the given direction
+    pub fn character_interact(
+        &mut self,
+        character: Entity,
+        direction: CardinalDirection,
+    ) -> Result<Option<ControlFlow>, ActionError> {
+        let coord =
+            self.spatial_table.coord_of(character).expect("character has no coord") + direction.coord();
+        // A robot standing in the way takes priority over the feature beneath it
+        let targets = self
+            .spatial_table
+            .layers_at(coord)
+            .map_or([None, None], |layers| [layers.character, layers.feature]);
+        match targets.into_iter().flatten().find_map(|target| {
+            INTERACTIONS.iter().find(|interaction| interaction.applies_to(self, target)).map(|i| (i, target))
+        }) {
+            Some((interaction, target)) => interaction.interact(self, character, target),
+            None => ActionError::nothing_to_interact_with(),
+        }
+    }
+
+    /// Called when a character walks into a solid feature
+    pub fn bump_feature(
+        &mut self,
+        character: Entity,
+        feature: Entity,
+    ) -> Result<Option<ControlFlow>, ActionError> {
+        match INTERACTIONS
+            .iter()
+            .find(|interaction| interaction.applies_to(self, feature) && interaction.on_bump(self, feature))
+        {
+            Some(interaction) => interaction.interact(self, character, feature),
+            None => ActionError::err_cant_walk_there(),
+        }
+    }
+}
 
 impl World {
     pub fn open_door(&mut self, entity: Entity) {
+        let tile =
+            if self.components.narrow_door.contains(entity) { Tile::NarrowDoorOpen } else { Tile::DoorOpen };
         self.components.apply_entity_update(
             entity,
             entity_update!(
                 solid: None,
                 door_state: Some(DoorState::Open),
-                tile: Some(Tile::DoorOpen),
+                tile: Some(tile),
                 opacity: Some(0),
             ),
         );
     }
 
     pub fn close_door(&mut self, entity: Entity) {
+        let tile = match self.components.locked.get(entity) {
+            Some(&clearance) => Tile::DoorLocked(clearance),
+            None if self.components.narrow_door.contains(entity) => Tile::NarrowDoorClosed,
+            None => Tile::DoorClosed,
+        };
         self.components.apply_entity_update(
             entity,
             entity_update!(
                 solid: Some(()),
                 door_state: Some(DoorState::Closed),
-                tile: Some(Tile::DoorClosed),
+                tile: Some(tile),
                 opacity: Some(255),
             ),
         );
     }
 
-    pub fn open_door_entity_adjacent_to_coord(&self, coord: Coord) -> Option<Entity> {
-        for direction in Direction::all() {
-            let potential_door_coord = coord + direction.coord();
-            if let Some(&Layers { feature: Some(feature_entity), .. }) =
-                self.spatial_table.layers_at(potential_door_coord)
-            {
-                if let Some(DoorState::Open) = self.components.door_state.get(feature_entity) {
-                    return Some(feature_entity);
-                }
-            }
-        }
-        None
-    }
-
     pub fn crush_grass(&mut self, entity: Entity) {
         self.components.insert_entity_data(
             entity,
@@ -93,9 +332,19 @@ impl World {
         }
 
         player.credit -= upgrade.level.cost();
+        self.unlock_upgrade(entity, upgrade);
+        Ok(())
+    }
+
+    /// Gives the player the upgrade without charging them for it
+    pub fn unlock_upgrade(&mut self, entity: Entity, upgrade: Upgrade) {
+        let player = self.components.player.get_mut(entity).unwrap();
         {
             let player_level = match upgrade.typ {
                 UpgradeType::Toughness => &mut player.upgrade_table.toughness,
+                UpgradeType::Accuracy => &mut player.upgrade_table.accuracy,
+                UpgradeType::Endurance => &mut player.upgrade_table.endurance,
+                UpgradeType::Sensors => &mut player.upgrade_table.sensors,
             };
 
             *player_level = Some(upgrade.level);
@@ -115,8 +364,13 @@ impl World {
             Upgrade { typ: Toughness, level: Level3 } => {
                 player.traits.explosive_damage = true;
             }
+            Upgrade { typ: Accuracy, level: Level2 | Level3 } => {
+                player.traits.double_damage = true;
+            }
+            // Hull breaches, the vacuum and the oxygen supply check the upgrade table directly
+            Upgrade { typ: Accuracy | Endurance, .. } => (),
+            // The motion tracker reads its range straight from the upgrade table
+            Upgrade { typ: Sensors, .. } => (),
         }
-
-        Ok(())
     }
 }
@@ -0,0 +1,144 @@
+use crate::prelude::*;
+use gridbugs::{
+    direction::Direction,
+    visible_area_detection::{vision_distance, Light, Rational},
+};
+
+// How quickly the station falls apart once the reactor is gone
+const FIRE_SPREAD_PER_TURN: usize = 4;
+const FIRE_DAMAGE: u32 = 1;
+const COLLAPSE_CHANCE: u32 = 30;
+const COLLAPSE_DAMAGE: u32 = 2;
+
+impl World {
+    pub fn elevator_coord(&self) -> Option<Coord> {
+        self.components.elevator.entities().find_map(|entity| self.entity_coord(entity))
+    }
+
+    pub fn is_elevator_at_coord(&self, coord: Coord) -> bool {
+        self.spatial_table
+            .layers_at(coord)
+            .and_then(|layers| layers.feature)
+            .map_or(false, |feature| self.components.elevator.contains(feature))
+    }
+
+    // Fire only spreads across dry floor that isn't blocked by a wall, door or machine. Grass burns
+    // away as the fire reaches it.
+    fn can_burn_at_coord(&self, coord: Coord) -> bool {
+        self.spatial_table.layers_at(coord).map_or(false, |layers| {
+            let dry_floor = layers.floor.map_or(false, |floor| {
+                !self.components.water.contains(floor) && !self.components.burning.contains(floor)
+            });
+            let open = layers.feature.map_or(true, |feature| self.components.grass_state.contains(feature));
+            dry_floor && open && !self.is_elevator_at_coord(coord)
+        })
+    }
+
+    /// Sets the floor at the given coord alight
+    pub fn ignite(&mut self, coord: Coord) {
+        if !self.can_burn_at_coord(coord) {
+            return;
+        }
+
+        let layers = self.spatial_table.layers_at_checked(coord);
+        if let Some(grass) = layers.feature {
+            self.components.dead.insert(grass, ());
+        }
+
+        if let Some(floor) = layers.floor {
+            self.components.burning.insert(floor, ());
+            self.components.tile.insert(floor, Tile::Fire);
+            self.components.light.insert(
+                floor,
+                Light {
+                    colour: Rgb24::new(255, 87, 31),
+                    vision_distance: vision_distance::Circle::new_squared(9),
+                    diminish: Rational { numerator: 1, denominator: 4 },
+                },
+            );
+        }
+    }
+
+    /// Spreads the fire to a few fresh cells next to ones that are already burning
+    pub fn spread_fire(&mut self) {
+        let mut candidates = self
+            .components
+            .burning
+            .entities()
+            .filter_map(|entity| self.entity_coord(entity))
+            .flat_map(|coord| CardinalDirection::all().map(move |direction| coord + direction.coord()))
+            .filter(|&coord| self.can_burn_at_coord(coord))
+            .collect::<Vec<_>>();
+        candidates.sort_by_key(|coord| (coord.x, coord.y));
+        candidates.dedup();
+        crate::rng::shuffle(&mut candidates);
+
+        for coord in candidates.into_iter().take(FIRE_SPREAD_PER_TURN) {
+            self.ignite(coord);
+        }
+    }
+
+    /// Damages every character standing in the fire
+    pub fn burn_characters(&mut self) {
+        let burning = self
+            .components
+            .burning
+            .entities()
+            .filter_map(|entity| self.entity_coord(entity))
+            .filter_map(|coord| self.get_character_at_coord(coord))
+            .collect::<Vec<_>>();
+
+        for character in burning {
+            if self.components.player.contains(character) {
+                crate::log::append_entry(Message::PlayerBurns);
+            }
+            self.damage_character(character, FIRE_DAMAGE);
+        }
+    }
+
+    // A wall can only come down if it's inside the station, as the outer hull is all that's keeping
+    // the vacuum out
+    fn is_collapsible_wall(&self, coord: Coord) -> bool {
+        !self.is_outer_hull(coord)
+            && self
+                .spatial_table
+                .layers_at(coord)
+                .and_then(|layers| layers.feature)
+                .and_then(|feature| self.components.tile.get(feature))
+                .map_or(false, |tile| matches!(tile, Tile::Wall | Tile::CaveWall))
+    }
+
+    /// Occasionally brings down a wall next to the fire, crushing anyone standing beside it
+    pub fn collapse_wall(&mut self) {
+        if crate::rng::range(0..100) >= COLLAPSE_CHANCE {
+            return;
+        }
+
+        let mut walls = self
+            .components
+            .burning
+            .entities()
+            .filter_map(|entity| self.entity_coord(entity))
+            .flat_map(|coord| CardinalDirection::all().map(move |direction| coord + direction.coord()))
+            .filter(|&coord| self.is_collapsible_wall(coord))
+            .collect::<Vec<_>>();
+        crate::rng::shuffle(&mut walls);
+
+        let wall_coord = if let Some(coord) = walls.pop() { coord } else { return };
+        let wall = self.spatial_table.layers_at_checked(wall_coord).feature.expect("wall not found");
+        self.components.solid.remove(wall);
+        self.components.opacity.insert(wall, 128);
+        self.components.tile.insert(wall, Tile::Rubble);
+        // Only walls without a floor beneath them need one
+        let _ = self.spawn_floor(wall_coord);
+
+        crate::log::append_entry(Message::WallCollapses);
+        crate::event::add_event(ExternalEvent::Explosion(wall_coord));
+
+        for direction in Direction::all() {
+            if let Some(character) = self.get_character_at_coord(wall_coord + direction.coord()) {
+                self.damage_character(character, COLLAPSE_DAMAGE);
+            }
+        }
+    }
+}
@@ -2,14 +2,30 @@ use crate::prelude::*;
 
 mod abilities;
 mod character;
+mod chasm;
 mod damage;
 mod error;
+mod flashlight;
 mod interaction;
+mod meltdown;
 mod movement;
+mod power;
+mod reactor;
+mod secrets;
+mod security;
+mod vacuum;
 
 pub use abilities::*;
 pub use character::*;
+pub use chasm::*;
 pub use damage::*;
 pub use error::*;
+pub use flashlight::*;
 pub use interaction::*;
+pub use meltdown::*;
 pub use movement::*;
+pub use power::*;
+pub use reactor::*;
+pub use secrets::*;
+pub use security::*;
+pub use vacuum::*;
@@ -31,6 +31,7 @@ impl World {
                         if let Some(&projectile_damage) =
                             self.components.projectile_damage.get(projectile_entity)
                         {
+                            self.try_breach_hull(entity_in_cell, &projectile_damage);
                             if self.components.hp.contains(entity_in_cell) {
                                 self.apply_projectile_damage(
                                     projectile_entity,
@@ -64,6 +65,14 @@ impl World {
                     }
                 }
 
+                if self.components.character.contains(projectile_entity)
+                    && self.is_chasm_at_coord(next_coord)
+                    && !self.entity_movement_profile(projectile_entity).crosses_chasms()
+                {
+                    self.push_over_chasm(projectile_entity, next_coord);
+                    return;
+                }
+
                 let _ignore_err = self.spatial_table.update_coord(projectile_entity, next_coord);
             } else {
                 self.projectile_stop(projectile_entity);
@@ -0,0 +1,99 @@
+use crate::prelude::*;
+use gridbugs::grid_2d::Grid;
+use std::collections::VecDeque;
+
+impl World {
+    /// Connects every light, door, socket, turret and floor tile to a generator. Conduits run beneath
+    /// the floor and through doorways, so each floor tile is fed by the generator with the shortest
+    /// run of conduit to it, and each generator ends up powering one wing of the floor. Sockets in
+    /// the walls are fed from the floor beside them. Floors without generators are left unwired,
+    /// and unwired entities are always powered.
+    pub fn wire_power_grid(&mut self) {
+        let mut sources: Grid<Option<Entity>> = Grid::new_default(self.size());
+        let mut queue = VecDeque::new();
+        for generator in self.components.generator.entities() {
+            if let Some(coord) = self.entity_coord(generator) {
+                *sources.get_checked_mut(coord) = Some(generator);
+                queue.push_back(coord);
+            }
+        }
+
+        while let Some(coord) = queue.pop_front() {
+            let source = *sources.get_checked(coord);
+            for direction in CardinalDirection::all() {
+                let neighbour = coord + direction.coord();
+                if self.spatial_table.layers_at(neighbour).map_or(true, |layers| layers.floor.is_none()) {
+                    continue;
+                }
+                if let Some(cell @ None) = sources.get_mut(neighbour) {
+                    *cell = source;
+                    queue.push_back(neighbour);
+                }
+            }
+        }
+
+        let turrets = self
+            .components
+            .npc
+            .iter()
+            .filter(|(_, npc)| npc.npc_type == NpcType::Turret)
+            .map(|(entity, _)| entity);
+        let consumers = self
+            .components
+            .light
+            .entities()
+            .chain(self.components.door_state.entities())
+            .chain(self.components.power_socket.entities())
+            .chain(turrets)
+            .chain(self.spatial_table.enumerate().filter_map(|(_, layers)| layers.floor))
+            .collect::<Vec<_>>();
+
+        for entity in consumers {
+            // The player carries their own light, which doesn't need the grid
+            if self.components.player.contains(entity) {
+                continue;
+            }
+            let coord = if let Some(coord) = self.entity_coord(entity) { coord } else { continue };
+            let source = sources.get_checked(coord).or_else(|| {
+                CardinalDirection::all()
+                    .find_map(|direction| sources.get(coord + direction.coord()).copied().flatten())
+            });
+            if let Some(source) = source {
+                self.components.power_source.insert(entity, source);
+            }
+        }
+    }
+
+    /// Returns true iff the entity isn't wired to the grid, or the generator it is wired to still runs
+    pub fn is_powered(&self, entity: Entity) -> bool {
+        self.components.power_source.get(entity).map_or(true, |&generator| self.entity_exists(generator))
+    }
+
+    pub fn is_powered_at(&self, coord: Coord) -> bool {
+        self.spatial_table
+            .layers_at(coord)
+            .and_then(|layers| layers.floor)
+            .map_or(true, |floor| self.is_powered(floor))
+    }
+
+    /// Blacks out the wing fed by a generator that was just destroyed. Its lights go out on their own
+    /// as lighting skips unpowered lights, and its doors spring open and stay jammed that way so that
+    /// no part of the floor gets sealed off. Locked doors stay shut, as they still need a keycard.
+    pub fn power_down(&mut self, generator: Entity) {
+        crate::log::append_entry(Message::Blackout);
+        let doors = self
+            .components
+            .power_source
+            .iter()
+            .filter(|&(entity, &source)| {
+                source == generator
+                    && self.components.door_state.contains(entity)
+                    && !self.components.locked.contains(entity)
+            })
+            .map(|(entity, _)| entity)
+            .collect::<Vec<_>>();
+        for door in doors {
+            self.open_door(door);
+        }
+    }
+}
@@ -0,0 +1,97 @@
+use crate::{prelude::*, world::explosion};
+
+// Vents and reinforcements appear in a ring around the reactor
+const REACTOR_RING_MIN_DISTANCE_SQUARED: u32 = 9;
+const REACTOR_RING_MAX_DISTANCE_SQUARED: u32 = 64;
+const VENTS_PER_PHASE: usize = 3;
+
+impl World {
+    pub fn reactor_entity(&self) -> Option<Entity> {
+        self.components.reactor.entities().find(|&entity| self.entity_exists(entity))
+    }
+
+    /// The reactor can't be damaged while any of its shield generators are still running
+    pub fn is_reactor_shielded(&self) -> bool {
+        self.components.shield_generator.entities().any(|entity| self.entity_exists(entity))
+    }
+
+    pub fn reactor_info(&self) -> Option<ReactorInfo> {
+        let reactor_entity = self.reactor_entity()?;
+        let reactor = self.components.reactor.get(reactor_entity)?;
+        let &hit_points = self.components.hp.get(reactor_entity)?;
+        let shield_generators =
+            self.components.shield_generator.entities().filter(|&entity| self.entity_exists(entity)).count();
+
+        Some(ReactorInfo {
+            phase: reactor.phase,
+            shielded: shield_generators > 0,
+            hit_points,
+            shield_generators,
+        })
+    }
+
+    // Returns empty floor coords around the reactor in a random order
+    fn coords_around_reactor(&self, reactor_coord: Coord) -> Vec<Coord> {
+        let mut coords = self
+            .spatial_table
+            .enumerate()
+            .filter_map(|(coord, layers)| {
+                let distance_squared = coord.distance2(reactor_coord);
+                (layers.floor.is_some()
+                    && layers.feature.is_none()
+                    && layers.character.is_none()
+                    && (REACTOR_RING_MIN_DISTANCE_SQUARED..=REACTOR_RING_MAX_DISTANCE_SQUARED)
+                        .contains(&distance_squared))
+                .then_some(coord)
+            })
+            .collect::<Vec<_>>();
+        crate::rng::shuffle(&mut coords);
+        coords
+    }
+
+    /// Advances the reactor to its next phase once it has taken enough damage. Returns the coords
+    /// where reinforcements should be spawned if a new phase began.
+    pub fn update_reactor(&mut self) -> Option<Vec<Coord>> {
+        let reactor_entity = self.reactor_entity()?;
+        let reactor_coord = self.entity_coord(reactor_entity)?;
+        let &hit_points = self.components.hp.get(reactor_entity)?;
+        let shielded = self.is_reactor_shielded();
+        let reactor = self.components.reactor.get_mut(reactor_entity)?;
+
+        if !reactor.exposed && !shielded {
+            reactor.exposed = true;
+            crate::log::append_entry(Message::ReactorExposed);
+            crate::event::add_event(ExternalEvent::LoopMusic(Music::Reactor));
+        }
+
+        let phase = Reactor::phase_for(hit_points);
+        if phase <= reactor.phase {
+            return None;
+        }
+
+        reactor.phase = phase;
+        crate::log::append_entry(Message::ReactorPhase(phase));
+
+        let mut coords = self.coords_around_reactor(reactor_coord);
+        for vent_coord in coords.drain(coords.len().saturating_sub(VENTS_PER_PHASE)..).collect::<Vec<_>>() {
+            use explosion::spec::*;
+            explosion::explode(
+                self,
+                vent_coord,
+                Explosion {
+                    mechanics: Mechanics(2),
+                    particle_emitter: ParticleEmitter {
+                        duration: Duration::from_millis(300),
+                        num_particles_per_frame: 50,
+                        min_step: Duration::from_millis(50),
+                        max_step: Duration::from_millis(200),
+                        fade_duration: Duration::from_millis(400),
+                    },
+                },
+            );
+        }
+
+        coords.truncate(phase as usize + 1);
+        Some(coords)
+    }
+}
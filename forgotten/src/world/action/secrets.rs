@@ -0,0 +1,37 @@
+use crate::prelude::*;
+
+/// Hidden doors further away than this can't be found by searching
+const SEARCH_RANGE: i32 = 3;
+/// Chance of finding an adjacent hidden door with each turn of searching. Doors further away are
+/// proportionally harder to find.
+const SEARCH_PERCENT: i32 = 60;
+
+impl World {
+    /// Spends a turn feeling along the nearby walls for hidden doors
+    pub fn search(&mut self, entity: Entity) {
+        let coord = if let Some(coord) = self.entity_coord(entity) { coord } else { return };
+        let found = self
+            .components
+            .undiscovered
+            .entities()
+            .filter(|&door| {
+                self.entity_coord(door).map_or(false, |door_coord| {
+                    let delta = door_coord - coord;
+                    let distance = delta.x.abs().max(delta.y.abs());
+                    distance <= SEARCH_RANGE && crate::rng::range(0..100) < SEARCH_PERCENT / distance
+                })
+            })
+            .collect::<Vec<_>>();
+        for door in found {
+            self.reveal_secret_door(door);
+            crate::log::append_entry(Message::SecretDoorFound);
+        }
+    }
+
+    /// Turns a hidden door into an ordinary closed door
+    pub fn reveal_secret_door(&mut self, door: Entity) {
+        if self.components.undiscovered.remove(door).is_some() {
+            self.close_door(door);
+        }
+    }
+}
@@ -0,0 +1,92 @@
+use crate::prelude::*;
+
+impl World {
+    /// Tell every robot on the floor where the player was last seen
+    pub fn alert_all_npcs(&mut self, player_coord: Coord) {
+        for (_, npc) in
+            self.components.npc.iter_mut().filter(|(_, npc)| npc.disposition != Disposition::Allied)
+        {
+            npc.move_to = Some(player_coord);
+        }
+    }
+
+    pub fn update_camera(&mut self, camera_entity: Entity, player_coord: Option<Coord>) {
+        let spotted_player = if let Some(camera) = self.components.camera.get_mut(camera_entity) {
+            let already_spotted = camera.spotted_player;
+            camera.spotted_player = player_coord.is_some();
+            player_coord.filter(|_| !already_spotted)
+        } else {
+            None
+        };
+
+        if let Some(player_coord) = spotted_player {
+            crate::log::append_entry(Message::CameraSpottedPlayer);
+            self.alert_all_npcs(player_coord);
+        }
+    }
+
+    pub fn raise_alarm(&mut self, entity: Entity, player_entity: Entity) {
+        if !self.entity_exists(entity) {
+            return;
+        }
+
+        if let Some(player_coord) = self.entity_coord(player_entity) {
+            crate::log::append_entry(Message::AlarmRaised);
+            self.alert_all_npcs(player_coord);
+        }
+    }
+
+    /// Returns true iff the feature is a piece of security equipment which can be smashed in melee
+    pub fn is_security_feature(&self, entity: Entity) -> bool {
+        self.components.camera.contains(entity) || self.components.alarm_panel.contains(entity)
+    }
+
+    /// Cameras and alarm panels are set into walls, so rather than leaving a hole behind, a smashed
+    /// one turns into a pile of rubble which can be walked over and only partly blocks light
+    pub fn wreck_security_feature(&mut self, entity: Entity) {
+        self.components.camera.remove(entity);
+        self.components.alarm_panel.remove(entity);
+        self.components.hp.remove(entity);
+        self.components.armour.remove(entity);
+        self.components.solid.remove(entity);
+        self.components.opacity.insert(entity, 128);
+        self.components.tile.insert(entity, Tile::Rubble);
+        if let Some(coord) = self.entity_coord(entity) {
+            // Only walls without a floor beneath them need one
+            let _ = self.spawn_floor(coord);
+        }
+    }
+
+    /// Returns true iff the player can damage the feature by bumping into it
+    pub fn is_smashable_feature(&self, entity: Entity) -> bool {
+        self.is_security_feature(entity)
+            || self.components.reactor.contains(entity)
+            || self.components.shield_generator.contains(entity)
+            || self.components.power_relay.contains(entity)
+            || self.components.generator.contains(entity)
+    }
+
+    pub fn player_smash_feature(&mut self, player_entity: Entity, feature_entity: Entity) {
+        let player = self.components.player.get(player_entity).unwrap();
+        crate::event::add_event(ExternalEvent::SoundEffect(SoundEffect::Punch));
+
+        let armour = self.components.armour.get(feature_entity).map_or(0, |armour| armour.value);
+        if player.melee_pen() >= armour {
+            let dmg = player.melee_dmg();
+            self.damage_character(feature_entity, dmg);
+        }
+    }
+}
+
+// Turrets
+const TURRET_SHOCK_TURNS: u32 = 3;
+
+impl World {
+    /// A shock from the cattle prod always knocks a turret offline for a few turns
+    pub fn disable_turret(&mut self, turret_entity: Entity) {
+        if let Some(npc) = self.components.npc.get(turret_entity) {
+            crate::log::append_entry(Message::EnemyStunned(npc.npc_type));
+            self.components.stunned.insert(turret_entity, Stunned { turns: TURRET_SHOCK_TURNS });
+        }
+    }
+}
@@ -0,0 +1,143 @@
+use crate::{prelude::*, world::realtime};
+use gridbugs::grid_2d::Grid;
+use std::collections::VecDeque;
+
+/// Only shots that punch through anything, like the Railgun and the Fifty Cal, can breach the hull
+const HULL_BREACH_PEN: u32 = 100;
+const HULL_BREACH_PERCENT: u32 = 25;
+/// Air rushes out of every cell within this many steps of a breach, unless a closed door is in the way
+const VACUUM_RANGE: u32 = 12;
+/// Chance each turn that something loose is dragged a step towards the breach
+const VACUUM_PULL_PERCENT: u32 = 50;
+const OXYGEN_RECOVERY: u32 = 2;
+const LOW_OXYGEN: u32 = 5;
+const SUFFOCATION_DAMAGE: u32 = 1;
+
+impl World {
+    /// A stray shot that hits the outer hull might punch a hole in it
+    pub fn try_breach_hull(&mut self, wall: Entity, projectile_damage: &ProjectileDamage) {
+        if projectile_damage.pen < HULL_BREACH_PEN || self.components.hull_breach.contains(wall) {
+            return;
+        }
+        let coord = if let Some(coord) = self.entity_coord(wall) { coord } else { return };
+        if !self.is_outer_hull(coord) || !matches!(self.components.tile.get(wall), Some(Tile::Wall)) {
+            return;
+        }
+
+        // Careful aim keeps most shots off the hull
+        let careful =
+            self.components.player.iter().any(|(_, player)| player.upgrade_table.accuracy.is_some());
+        let chance = if careful { HULL_BREACH_PERCENT / 2 } else { HULL_BREACH_PERCENT };
+        if crate::rng::range(0..100) >= chance {
+            return;
+        }
+
+        self.components.hull_breach.insert(wall, ());
+        self.components.tile.insert(wall, Tile::HullBreach);
+        crate::log::append_entry(Message::HullBreach);
+        crate::event::add_event(ExternalEvent::Explosion(coord));
+    }
+
+    /// Maps each cell losing air to the breach it's draining into. Closed doors hold the air back.
+    fn vacuum(&self) -> Grid<Option<Coord>> {
+        let mut vacuum: Grid<Option<Coord>> = Grid::new_default(self.size());
+        let mut distances: Grid<u32> = Grid::new_default(self.size());
+        let mut queue = VecDeque::new();
+        for breach in self.components.hull_breach.entities() {
+            if let Some(coord) = self.entity_coord(breach) {
+                *vacuum.get_checked_mut(coord) = Some(coord);
+                queue.push_back(coord);
+            }
+        }
+
+        while let Some(coord) = queue.pop_front() {
+            let distance = *distances.get_checked(coord);
+            if distance >= VACUUM_RANGE {
+                continue;
+            }
+            let breach = *vacuum.get_checked(coord);
+            for direction in CardinalDirection::all() {
+                let neighbour = coord + direction.coord();
+                let solid = self
+                    .spatial_table
+                    .layers_at(neighbour)
+                    .and_then(|layers| layers.feature)
+                    .map_or(false, |feature| self.components.solid.contains(feature));
+                if solid {
+                    continue;
+                }
+                if let Some(cell @ None) = vacuum.get_mut(neighbour) {
+                    *cell = breach;
+                    *distances.get_checked_mut(neighbour) = distance + 1;
+                    queue.push_back(neighbour);
+                }
+            }
+        }
+        vacuum
+    }
+
+    /// Drags loose characters and items one step towards the breach they're venting into, and
+    /// uses up the player's air while they're in the vacuum
+    pub fn vacuum_turn(&mut self, player: Entity) {
+        if self.components.hull_breach.is_empty() {
+            self.breathe(player, false);
+            return;
+        }
+        let vacuum = self.vacuum();
+
+        // Good endurance halves the pull on the player
+        let braced = self.components.player.get(player).and_then(|p| p.upgrade_table.endurance).is_some();
+        let loose = self
+            .components
+            .character
+            .entities()
+            .chain(self.components.item.entities().filter(|&item| !self.components.solid.contains(item)))
+            .filter(|&entity| {
+                !self.is_stationary(entity) && !self.realtime_components.movement.contains(entity)
+            })
+            .filter_map(|entity| {
+                let coord = self.entity_coord(entity)?;
+                let breach = (*vacuum.get(coord)?)?;
+                Some((entity, breach - coord))
+            })
+            .filter(|&(_, path)| path != Coord::new(0, 0))
+            .collect::<Vec<_>>();
+        for (entity, path) in loose {
+            let percent =
+                if entity == player && braced { VACUUM_PULL_PERCENT / 2 } else { VACUUM_PULL_PERCENT };
+            if crate::rng::range(0..100) >= percent {
+                continue;
+            }
+            self.components.realtime.insert(entity, ());
+            self.realtime_components.movement.insert(
+                entity,
+                realtime::movement::spec::Movement {
+                    path,
+                    repeat: realtime::movement::spec::Repeat::Steps(1),
+                    cardinal_step_duration: Duration::from_millis(100),
+                }
+                .build(),
+            );
+        }
+
+        let in_vacuum = self.entity_coord(player).map_or(false, |coord| vacuum.get_checked(coord).is_some());
+        self.breathe(player, in_vacuum);
+    }
+
+    fn breathe(&mut self, entity: Entity, in_vacuum: bool) {
+        let player = if let Some(player) = self.components.player.get_mut(entity) { player } else { return };
+        if !in_vacuum {
+            player.oxygen = (player.oxygen + OXYGEN_RECOVERY).min(player.max_oxygen());
+            return;
+        }
+        if player.oxygen == 0 {
+            crate::log::append_entry(Message::Suffocating);
+            self.damage_character(entity, SUFFOCATION_DAMAGE);
+            return;
+        }
+        player.oxygen -= 1;
+        if player.oxygen == LOW_OXYGEN {
+            crate::log::append_entry(Message::LowOxygen);
+        }
+    }
+}
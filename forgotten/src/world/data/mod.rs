@@ -2,16 +2,24 @@ use crate::prelude::*;
 use gridbugs::{entity_table, visible_area_detection::*};
 
 mod npc;
+mod objective;
 mod player;
 mod projectile;
+mod reactor;
+mod security;
+mod sensors;
 mod stats;
 mod terrain;
 mod upgrade;
 mod weapon;
 
 pub use npc::*;
+pub use objective::*;
 pub use player::*;
 pub use projectile::*;
+pub use reactor::*;
+pub use security::*;
+pub use sensors::*;
 pub use stats::*;
 pub use terrain::*;
 pub use upgrade::*;
@@ -22,23 +30,43 @@ pub enum Tile {
     Wall,
     DoorOpen,
     DoorClosed,
+    DoorLocked(Clearance),
+    NarrowDoorOpen,
+    NarrowDoorClosed,
     Floor,
     CaveWall,
     CaveFloor,
     Grass,
     GrassCrushed,
     Water,
+    Chasm,
+    Bridge,
     Reactor,
+    ShieldGenerator,
     Stairs,
+    Elevator,
+    Fire,
+    Rubble,
+    HullBreach,
+    Camera(CardinalDirection),
+    AlarmPanel,
+    Terminal,
+    PowerRelay,
+    Generator,
+    PowerSocket,
+    ServerRack,
 
     // Entity
     Player,
     Bullet,
     Npc(NpcType),
+    AlliedNpc(NpcType),
 
     // Items
     Weapon(WeaponType),
     Medkit,
+    Keycard(Clearance),
+    DogTags,
     Upgrade,
     Credit1,
     Credit2,
@@ -48,13 +76,25 @@ pub enum Tile {
 #[derive(Debug, Clone, Copy, Serialize, Deserialize)]
 pub enum Item {
     Medkit,
+    Keycard(Clearance),
+    DogTags,
     Credit(u32),
     Weapon(WeaponType),
 }
 
 impl Tile {
     pub const fn is_wall(&self) -> bool {
-        matches!(self, Self::Wall | Self::DoorClosed | Self::DoorOpen | Self::CaveWall)
+        matches!(
+            self,
+            Self::Wall
+                | Self::DoorClosed
+                | Self::DoorLocked(_)
+                | Self::DoorOpen
+                | Self::NarrowDoorClosed
+                | Self::NarrowDoorOpen
+                | Self::CaveWall
+                | Self::HullBreach
+        )
     }
 }
 
@@ -70,9 +110,26 @@ entity_table::declare_entity_module! {
         solid: (),
         tile: Tile,
         grass_state: GrassState,
+        water: (),
+        chasm: (),
         door_state: DoorState,
+        locked: Clearance,
+        narrow_door: (),
         stairs: (),
+        elevator: (),
+        burning: (),
         upgrade: (),
+        camera: Camera,
+        alarm_panel: (),
+        terminal: LoreId,
+        power_relay: (),
+        generator: (),
+        power_socket: (),
+        power_source: Entity,
+        server_rack: (),
+        hull_breach: (),
+        secret_door: (),
+        undiscovered: (),
 
         //Entity
         npc: Npc,
@@ -81,7 +138,11 @@ entity_table::declare_entity_module! {
         stunned: Stunned,
         character: (),
         weapon: Weapon,
-        reactor: (),
+        reactor: Reactor,
+        shield_generator: (),
+        stationary: (),
+        hacked: Hacked,
+        moved: (),
 
         // Stats
         dead: (),
@@ -6,17 +6,115 @@ pub enum NpcType {
     SecBot,
     RoboCop,
     DoomBot,
+    Turret,
+    RepairBot,
+    ShieldBot,
+    Drone,
+    Skimmer,
+}
+
+impl NpcType {
+    pub const fn movement_profile(self) -> MovementProfile {
+        match self {
+            Self::DoomBot => MovementProfile::Heavy,
+            Self::Drone => MovementProfile::Flying,
+            Self::Skimmer => MovementProfile::Amphibious,
+            Self::MiniBot
+            | Self::SecBot
+            | Self::RoboCop
+            | Self::Turret
+            | Self::RepairBot
+            | Self::ShieldBot => MovementProfile::Walker,
+        }
+    }
+
+    /// The locked doors a robot of this type can open on its own
+    pub const fn clearance(self) -> Option<Clearance> {
+        match self {
+            Self::SecBot => Some(Clearance::Blue),
+            Self::RoboCop | Self::RepairBot | Self::ShieldBot => Some(Clearance::Yellow),
+            Self::DoomBot => Some(Clearance::Red),
+            Self::MiniBot | Self::Turret | Self::Drone | Self::Skimmer => None,
+        }
+    }
+
+    pub const fn mobility(self) -> Mobility {
+        Mobility { profile: self.movement_profile(), clearance: self.clearance() }
+    }
+}
+
+/// How a robot gets around the station
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum MovementProfile {
+    /// Walks and wades anywhere the player can, opening doors as it goes
+    Walker,
+    /// Flies over water, chasms and grass without disturbing any of them
+    Flying,
+    /// Too bulky to squeeze through narrow doors
+    Heavy,
+    /// At home both on land and in water, but prefers the water
+    Amphibious,
+}
+
+impl MovementProfile {
+    pub const ALL: [Self; 4] = [Self::Walker, Self::Flying, Self::Heavy, Self::Amphibious];
+
+    pub const fn index(self) -> usize {
+        self as usize
+    }
+
+    pub const fn crosses_chasms(self) -> bool {
+        matches!(self, Self::Flying)
+    }
+
+    pub const fn fits_through_narrow_doors(self) -> bool {
+        !matches!(self, Self::Heavy)
+    }
+
+    pub const fn crushes_grass(self) -> bool {
+        !matches!(self, Self::Flying)
+    }
+}
+
+/// Everything which decides where a robot can go: how it moves, and which locked doors it can open
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Mobility {
+    pub profile: MovementProfile,
+    pub clearance: Option<Clearance>,
+}
+
+impl Mobility {
+    // Robots without any clearance take the first slot for each profile
+    const CLEARANCE_SLOTS: usize = Clearance::ALL.len() + 1;
+
+    pub const COUNT: usize = MovementProfile::ALL.len() * Self::CLEARANCE_SLOTS;
+
+    pub fn all() -> impl Iterator<Item = Self> {
+        MovementProfile::ALL.into_iter().flat_map(|profile| {
+            std::iter::once(None)
+                .chain(Clearance::ALL.into_iter().map(Some))
+                .map(move |clearance| Self { profile, clearance })
+        })
+    }
+
+    pub const fn index(self) -> usize {
+        let clearance_slot = match self.clearance {
+            None => 0,
+            Some(clearance) => clearance.index() + 1,
+        };
+        self.profile.index() * Self::CLEARANCE_SLOTS + clearance_slot
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum Disposition {
     Hostile,
     Afraid,
+    Allied,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Npc {
-    pub drop_chance: u8,
     pub npc_type: NpcType,
     pub weapon: Option<Weapon>,
     pub move_to: Option<Coord>,
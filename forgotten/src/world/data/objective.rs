@@ -0,0 +1,43 @@
+use crate::prelude::*;
+
+/// An optional goal on a floor. Completing it earns a reward, but the player is free to head
+/// straight for the stairs instead.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ObjectiveType {
+    /// Smash the power relay feeding the floor's security systems
+    DestroyRelay,
+    /// Pick up the dog tags of a fallen member of the forward team
+    RecoverDogTags,
+    /// Destroy every robot guarding the server room
+    ClearServerRoom,
+}
+
+impl ObjectiveType {
+    pub const ALL: [Self; 3] = [Self::DestroyRelay, Self::RecoverDogTags, Self::ClearServerRoom];
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Reward {
+    Credit(u32),
+    /// The next upgrade in the store, free of charge
+    Upgrade,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Objective {
+    pub typ: ObjectiveType,
+    pub reward: Reward,
+    pub complete: bool,
+    /// The objective is complete once none of these entities exist any more
+    targets: Vec<Entity>,
+}
+
+impl Objective {
+    pub const fn new(typ: ObjectiveType, reward: Reward, targets: Vec<Entity>) -> Self {
+        Self { typ, reward, complete: false, targets }
+    }
+
+    pub fn targets(&self) -> &[Entity] {
+        &self.targets
+    }
+}
@@ -3,20 +3,40 @@ use crate::prelude::*;
 #[derive(Default, Debug, Clone, Serialize, Deserialize)]
 pub struct Player {
     pub credit: u32,
+    pub clearance: Option<Clearance>,
     pub traits: PlayerTraits,
     pub melee_weapon: Weapon,
     pub upgrade_table: UpgradeTable,
     pub ranged_weapons: Vec<Option<Weapon>>,
+    #[serde(default)]
+    pub flashlight: Flashlight,
+    /// Logs read so far, in the order they were found
+    #[serde(default)]
+    pub codex: Vec<LoreId>,
+    /// Turns of air left while standing in a vacuum
+    #[serde(default = "full_oxygen")]
+    pub oxygen: u32,
 }
 
 impl Player {
     pub fn new() -> Self {
         Self {
             credit: 0,
+            clearance: None,
             traits: Default::default(),
             ranged_weapons: vec![None, None],
             melee_weapon: Weapon::new_bare_hands(),
-            upgrade_table: UpgradeTable { toughness: None, accuracy: None, endurance: None },
+            upgrade_table: UpgradeTable { toughness: None, accuracy: None, endurance: None, sensors: None },
+            flashlight: Flashlight::default(),
+            codex: Vec::new(),
+            oxygen: OXYGEN_SUPPLY,
+        }
+    }
+
+    pub fn max_oxygen(&self) -> u32 {
+        match self.upgrade_table.endurance {
+            Some(UpgradeLevel::Level2 | UpgradeLevel::Level3) => OXYGEN_SUPPLY * 2,
+            _ => OXYGEN_SUPPLY,
         }
     }
 
@@ -52,11 +72,59 @@ impl Player {
             }
             Some(UpgradeLevel::Level3) => (),
         }
+        match self.upgrade_table.accuracy {
+            None => out.push(Upgrade { typ: UpgradeType::Accuracy, level: UpgradeLevel::Level1 }),
+            Some(UpgradeLevel::Level1) => {
+                out.push(Upgrade { typ: UpgradeType::Accuracy, level: UpgradeLevel::Level2 })
+            }
+            Some(UpgradeLevel::Level2 | UpgradeLevel::Level3) => (),
+        }
+        match self.upgrade_table.endurance {
+            None => out.push(Upgrade { typ: UpgradeType::Endurance, level: UpgradeLevel::Level1 }),
+            Some(UpgradeLevel::Level1) => {
+                out.push(Upgrade { typ: UpgradeType::Endurance, level: UpgradeLevel::Level2 })
+            }
+            Some(UpgradeLevel::Level2 | UpgradeLevel::Level3) => (),
+        }
+        match self.upgrade_table.sensors {
+            None => out.push(Upgrade { typ: UpgradeType::Sensors, level: UpgradeLevel::Level1 }),
+            Some(UpgradeLevel::Level1) => {
+                out.push(Upgrade { typ: UpgradeType::Sensors, level: UpgradeLevel::Level2 })
+            }
+            Some(UpgradeLevel::Level2) => {
+                out.push(Upgrade { typ: UpgradeType::Sensors, level: UpgradeLevel::Level3 })
+            }
+            Some(UpgradeLevel::Level3) => (),
+        }
 
         out
     }
 }
 
+/// Number of turns the player can hold their breath in a vacuum before they start to suffocate
+pub const OXYGEN_SUPPLY: u32 = 20;
+
+const fn full_oxygen() -> u32 {
+    OXYGEN_SUPPLY
+}
+
+/// Number of turns a full battery keeps the flashlight on for
+pub const FLASHLIGHT_BATTERY: u32 = 150;
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct Flashlight {
+    pub on: bool,
+    pub battery: u32,
+    /// The beam points in the direction the player last moved or aimed
+    pub facing: CardinalDirection,
+}
+
+impl Default for Flashlight {
+    fn default() -> Self {
+        Self { on: false, battery: FLASHLIGHT_BATTERY, facing: CardinalDirection::North }
+    }
+}
+
 #[derive(Serialize, Deserialize)]
 pub struct CharacterInfo {
     pub coord: Coord,
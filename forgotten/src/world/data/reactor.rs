@@ -0,0 +1,52 @@
+use crate::prelude::*;
+
+/// Number of phases the reactor goes through before it is destroyed. A new phase begins each time
+/// the reactor loses another third of its health.
+pub const REACTOR_PHASES: u8 = 3;
+
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+pub struct Reactor {
+    pub phase: u8,
+    pub exposed: bool,
+}
+
+impl Reactor {
+    /// Returns the phase the reactor should be in given its current health
+    pub const fn phase_for(hit_points: HitPoints) -> u8 {
+        let lost = hit_points.max - hit_points.current;
+        let phase = (lost * REACTOR_PHASES as u32 / hit_points.max) as u8;
+        if phase >= REACTOR_PHASES {
+            REACTOR_PHASES - 1
+        } else {
+            phase
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct ReactorInfo {
+    pub phase: u8,
+    pub shielded: bool,
+    pub hit_points: HitPoints,
+    pub shield_generators: usize,
+}
+
+/// Number of turns the player has to reach the extraction elevator once the reactor is destroyed
+pub const MELTDOWN_TURNS: u32 = 75;
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct Meltdown {
+    pub turns_remaining: u32,
+}
+
+impl Meltdown {
+    pub const fn new() -> Self {
+        Self { turns_remaining: MELTDOWN_TURNS }
+    }
+}
+
+impl Default for Meltdown {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,50 @@
+use crate::prelude::*;
+use std::fmt;
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct Camera {
+    pub facing: CardinalDirection,
+    pub spotted_player: bool,
+}
+
+impl Camera {
+    pub const fn new(facing: CardinalDirection) -> Self {
+        Self { facing, spotted_player: false }
+    }
+}
+
+/// Security clearance needed to open a locked door. A keycard opens every door at or below its own
+/// clearance.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum Clearance {
+    Blue,
+    Yellow,
+    Red,
+}
+
+impl Clearance {
+    pub const ALL: [Self; 3] = [Self::Blue, Self::Yellow, Self::Red];
+
+    pub const fn index(self) -> usize {
+        self as usize
+    }
+
+    /// The clearances which have locked doors on the given floor
+    pub fn for_level(level: u8) -> impl Iterator<Item = Self> {
+        Self::ALL.into_iter().take(level.min(Self::ALL.len() as u8) as usize)
+    }
+
+    pub fn can_open(clearance: Option<Self>, required: Self) -> bool {
+        clearance.map_or(false, |clearance| clearance >= required)
+    }
+}
+
+impl fmt::Display for Clearance {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Blue => write!(f, "Blue"),
+            Self::Yellow => write!(f, "Yellow"),
+            Self::Red => write!(f, "Red"),
+        }
+    }
+}
@@ -0,0 +1,32 @@
+use crate::prelude::*;
+
+/// A robot which moved during the last turn, as picked up by the player's motion tracker
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MotionPing {
+    /// Position of the robot relative to the player
+    pub offset: Coord,
+}
+
+impl MotionPing {
+    /// Distance to the robot in cells, ignoring walls
+    pub fn distance(&self) -> u32 {
+        self.offset.x.unsigned_abs().max(self.offset.y.unsigned_abs())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MotionTracker {
+    /// Robots further away than this many cells are not picked up
+    pub range: u32,
+    pub pings: Vec<MotionPing>,
+}
+
+impl MotionTracker {
+    pub const fn range_for(level: UpgradeLevel) -> u32 {
+        match level {
+            UpgradeLevel::Level1 => 8,
+            UpgradeLevel::Level2 => 12,
+            UpgradeLevel::Level3 => 16,
+        }
+    }
+}
@@ -27,3 +27,10 @@ impl HitPoints {
 pub struct Stunned {
     pub turns: u32,
 }
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct Hacked {
+    pub turns: u32,
+    /// The disposition to go back to once the hack wears off
+    pub disposition: Disposition,
+}
@@ -12,12 +12,19 @@ use crate::prelude::*;
 //   1. Half effect of vacumm pull
 //   2. Double oxygen
 //
+// Sensors:
+//   1. Motion tracker
+//   2. Longer tracker range
+//   3. Longest tracker range
+//
 
 #[derive(Default, Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct UpgradeTable {
     pub toughness: Option<UpgradeLevel>,
     pub accuracy: Option<UpgradeLevel>,
     pub endurance: Option<UpgradeLevel>,
+    #[serde(default)]
+    pub sensors: Option<UpgradeLevel>,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
@@ -40,6 +47,9 @@ impl UpgradeLevel {
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum UpgradeType {
     Toughness,
+    Accuracy,
+    Endurance,
+    Sensors,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
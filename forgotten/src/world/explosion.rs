@@ -2,7 +2,7 @@ use crate::{
     world::{realtime, ExternalEvent, World},
     Entity,
 };
-use crate::{EntityData, Layers};
+use crate::{EntityData, Layers, Message};
 use gridbugs::{coord_2d::Coord, direction::Direction, entity_table::entity_data, line_2d::LineSegment};
 use std::time::Duration;
 
@@ -58,20 +58,22 @@ fn apply_indirect_hit(
     let CharacterEffect { push_back, damage } =
         character_effect_indirect_hit(mechanics, explosion_to_character);
 
-    world.components.insert_entity_data(
-        character_entity,
-        entity_data!(realtime: (), pushed_from: world.spatial_table.coord_of(character_entity).unwrap()),
-    );
-
-    world.realtime_components.movement.insert(
-        character_entity,
-        realtime::movement::spec::Movement {
-            path: explosion_to_character.delta(),
-            repeat: realtime::movement::spec::Repeat::Steps(push_back as usize),
-            cardinal_step_duration: Duration::from_millis(100),
-        }
-        .build(),
-    );
+    if !world.is_stationary(character_entity) {
+        world.components.insert_entity_data(
+            character_entity,
+            entity_data!(realtime: (), pushed_from: world.spatial_table.coord_of(character_entity).unwrap()),
+        );
+
+        world.realtime_components.movement.insert(
+            character_entity,
+            realtime::movement::spec::Movement {
+                path: explosion_to_character.delta(),
+                repeat: realtime::movement::spec::Repeat::Steps(push_back as usize),
+                cardinal_step_duration: Duration::from_millis(100),
+            }
+            .build(),
+        );
+    }
 
     world.damage_character(character_entity, damage);
 }
@@ -96,7 +98,7 @@ fn apply_direct_hit(
 
     if solid_neighbour_vector.is_zero() {
         log::warn!("Direct hit with no solid neighbours shouldn't be possible.");
-    } else {
+    } else if !world.is_stationary(character_entity) {
         let travel_vector = -solid_neighbour_vector;
 
         world.components.insert_entity_data(
@@ -137,6 +139,45 @@ fn apply_mechanics(world: &mut World, explosion_coord: Coord, mechanics: &spec::
             }
         }
     }
+
+    // Security equipment and shield generators are bolted down, so they take damage without being
+    // pushed back
+    let security_features = world
+        .components
+        .hp
+        .entities()
+        .filter(|&entity| {
+            world.is_security_feature(entity) || world.components.shield_generator.contains(entity)
+        })
+        .collect::<Vec<_>>();
+    for feature_entity in security_features {
+        if let Some(feature_coord) = world.spatial_table.coord_of(feature_entity) {
+            if is_in_explosion_range(explosion_coord, mechanics, feature_coord) {
+                let explosion_to_feature = LineSegment::new(explosion_coord, feature_coord);
+                let CharacterEffect { damage, .. } =
+                    character_effect_indirect_hit(mechanics, explosion_to_feature);
+                world.damage_character(feature_entity, damage);
+            }
+        }
+    }
+
+    // The blast knocks open any hidden doors it reaches
+    let secret_doors = world
+        .components
+        .undiscovered
+        .entities()
+        .filter(|&door| {
+            world
+                .spatial_table
+                .coord_of(door)
+                .map_or(false, |door_coord| is_in_explosion_range(explosion_coord, mechanics, door_coord))
+        })
+        .collect::<Vec<_>>();
+    for door in secret_doors {
+        world.reveal_secret_door(door);
+        world.open_door(door);
+        crate::log::append_entry(Message::SecretDoorBlownOpen);
+    }
 }
 
 pub fn explode(world: &mut World, coord: Coord, explosion: spec::Explosion) {
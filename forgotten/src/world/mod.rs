@@ -18,6 +18,7 @@ pub use explosion::*;
 pub use query::*;
 pub use realtime::*;
 pub use spatial::*;
+pub use spawner::*;
 pub use sys::*;
 pub use visibility::*;
 
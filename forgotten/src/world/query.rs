@@ -1,5 +1,7 @@
 use crate::prelude::*;
 
+const SHIELD_ARMOUR_BONUS: u32 = 2;
+
 // Spatial
 impl World {
     pub fn entity_coord(&self, entity: Entity) -> Option<Coord> {
@@ -10,15 +12,73 @@ impl World {
         self.spatial_table.layers_at(coord).and_then(|cell| cell.character)
     }
 
-    pub fn can_npc_traverse_feature_at_coord(&self, coord: Coord) -> bool {
+    pub fn is_water_at_coord(&self, coord: Coord) -> bool {
+        self.spatial_table
+            .layers_at(coord)
+            .and_then(|cell| cell.floor)
+            .map_or(false, |floor| self.components.water.contains(floor))
+    }
+
+    pub fn is_chasm_at_coord(&self, coord: Coord) -> bool {
+        self.spatial_table
+            .layers_at(coord)
+            .and_then(|cell| cell.floor)
+            .map_or(false, |floor| self.components.chasm.contains(floor))
+    }
+
+    /// The outer hull is the ring of cells around the edge of the map. Space lies beyond it.
+    pub fn is_outer_hull(&self, coord: Coord) -> bool {
+        let size = self.size();
+        coord.x <= 0 || coord.y <= 0 || coord.x >= size.x() as i32 - 1 || coord.y >= size.y() as i32 - 1
+    }
+
+    pub fn is_allied(&self, entity: Entity) -> bool {
+        self.components.npc.get(entity).map_or(false, |npc| npc.disposition == Disposition::Allied)
+    }
+
+    /// Returns true iff exactly one of the two robots has been hacked onto the player's side
+    pub fn are_robots_hostile(&self, a: Entity, b: Entity) -> bool {
+        self.components.npc.contains(a)
+            && self.components.npc.contains(b)
+            && self.is_allied(a) != self.is_allied(b)
+    }
+
+    pub fn entity_movement_profile(&self, entity: Entity) -> MovementProfile {
+        self.components
+            .npc
+            .get(entity)
+            .map_or(MovementProfile::Walker, |npc| npc.npc_type.movement_profile())
+    }
+
+    /// The best keycard the player has picked up, or the clearance built into a robot
+    pub fn entity_clearance(&self, entity: Entity) -> Option<Clearance> {
+        if let Some(player) = self.components.player.get(entity) {
+            player.clearance
+        } else {
+            self.components.npc.get(entity).and_then(|npc| npc.npc_type.clearance())
+        }
+    }
+
+    /// Returns true iff the door can be opened by a character with the given clearance
+    pub fn can_open_door(&self, door: Entity, clearance: Option<Clearance>) -> bool {
+        self.components.locked.get(door).map_or(true, |&required| Clearance::can_open(clearance, required))
+    }
+
+    pub fn can_npc_traverse_feature_at_coord(&self, coord: Coord, mobility: Mobility) -> bool {
         if let Some(spatial_cell) = self.spatial_table.layers_at(coord) {
-            if spatial_cell.floor.is_none() {
+            let floor = if let Some(floor) = spatial_cell.floor { floor } else { return false };
+            if self.components.chasm.contains(floor) && !mobility.profile.crosses_chasms() {
                 return false;
             }
             spatial_cell.feature.map_or(true, |feature| {
-                self.components.door_state.contains(feature)
-                    || self.components.grass_state.contains(feature)
-                    || !(self.components.solid.contains(feature))
+                if self.components.door_state.contains(feature) {
+                    (mobility.profile.fits_through_narrow_doors()
+                        || !self.components.narrow_door.contains(feature))
+                        && self.can_open_door(feature, mobility.clearance)
+                } else {
+                    self.components.grass_state.contains(feature)
+                        || !(self.components.solid.contains(feature))
+                }
             })
         } else {
             false
@@ -73,6 +133,41 @@ impl World {
         Some(CharacterInfo { coord, hit_points, stunned })
     }
 
+    /// Stationary characters such as turrets are bolted to the floor and can't be knocked back
+    pub fn is_stationary(&self, entity: Entity) -> bool {
+        self.components.stationary.contains(entity)
+    }
+
+    /// Robots standing next to a living shield bot get extra armour
+    pub fn shield_bonus(&self, entity: Entity) -> u32 {
+        if !self.components.npc.contains(entity) {
+            return 0;
+        }
+
+        let coord = if let Some(coord) = self.entity_coord(entity) { coord } else { return 0 };
+        let shielded = Direction::all().any(|direction| {
+            self.get_character_at_coord(coord + direction.coord()).map_or(false, |neighbour| {
+                self.entity_exists(neighbour)
+                    && !self.are_robots_hostile(entity, neighbour)
+                    && self
+                        .components
+                        .npc
+                        .get(neighbour)
+                        .map_or(false, |npc| npc.npc_type == NpcType::ShieldBot)
+            })
+        });
+
+        if shielded {
+            SHIELD_ARMOUR_BONUS
+        } else {
+            0
+        }
+    }
+
+    pub fn effective_armour(&self, entity: Entity) -> Option<u32> {
+        self.components.armour.get(entity).map(|armour| armour.value + self.shield_bonus(entity))
+    }
+
     pub fn check_movement_blocked(&self, entity: Entity) -> bool {
         let is_blocked_mov = self.entity_coord(entity).map_or(false, |coord| {
             self.components.pushed_from.get(entity).map_or(false, |from| from.manhattan_distance(coord) > 1)
@@ -105,4 +200,22 @@ impl World {
     pub fn is_gameplay_blocked(&self) -> bool {
         !self.components.blocks_gameplay.is_empty()
     }
+
+    /// Hostile robots which moved last turn within range of the player's motion tracker. Walls
+    /// don't block the tracker. Returns `None` if the player hasn't installed one.
+    pub fn motion_tracker(&self, player: Entity) -> Option<MotionTracker> {
+        let level = self.components.player.get(player)?.upgrade_table.sensors?;
+        let range = MotionTracker::range_for(level);
+        let player_coord = self.entity_coord(player)?;
+        let pings = self
+            .components
+            .moved
+            .entities()
+            .filter(|&entity| !self.is_allied(entity))
+            .filter_map(|entity| self.entity_coord(entity))
+            .map(|coord| MotionPing { offset: coord - player_coord })
+            .filter(|ping| ping.distance() <= range)
+            .collect();
+        Some(MotionTracker { range, pings })
+    }
 }
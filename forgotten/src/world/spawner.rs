@@ -5,18 +5,39 @@ use gridbugs::{
     entity_table::{entity_data, Entity},
     visible_area_detection::{vision_distance, Light, Rational},
 };
+use std::fmt;
 use vector::Radians;
 
+/// An entity couldn't be spawned because something already occupies its layer of the cell
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CellOccupied {
+    pub coord: Coord,
+    pub layer: Option<Layer>,
+}
+
+impl fmt::Display for CellOccupied {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "there is already a {:?} at {:?}", self.layer, self.coord)
+    }
+}
+
+impl std::error::Error for CellOccupied {}
+
 impl World {
-    /// Helper method to spawn an entity at a location
-    fn spawn_entity<L: Into<Location>>(&mut self, location: L, entity_data: EntityData) -> Entity {
+    /// Helper method to spawn an entity at a location. Fails if something is already there.
+    fn spawn_entity<L: Into<Location>>(
+        &mut self,
+        location: L,
+        entity_data: EntityData,
+    ) -> Result<Entity, CellOccupied> {
         let entity = self.entity_allocator.alloc();
         let location @ Location { layer, coord } = location.into();
-        if let Err(e) = self.spatial_table.update(entity, location) {
-            panic!("{:?}: There is already a {:?} at {:?}", e, layer, coord);
+        if self.spatial_table.update(entity, location).is_err() {
+            self.entity_allocator.free(entity);
+            return Err(CellOccupied { coord, layer });
         }
         self.components.insert_entity_data(entity, entity_data);
-        entity
+        Ok(entity)
     }
 
     pub fn insert_entity_data(&mut self, location: Location, entity_data: EntityData) -> Entity {
@@ -41,7 +62,7 @@ impl World {
         );
     }
 
-    pub fn spawn_wall(&mut self, coord: Coord) {
+    pub fn spawn_wall(&mut self, coord: Coord) -> Result<(), CellOccupied> {
         self.spawn_entity(
             (coord, Layer::Feature),
             entity_data! {
@@ -49,10 +70,11 @@ impl World {
                 solid: (),
                 opacity: 255,
             },
-        );
+        )?;
+        Ok(())
     }
 
-    pub fn spawn_cave_wall(&mut self, coord: Coord) {
+    pub fn spawn_cave_wall(&mut self, coord: Coord) -> Result<(), CellOccupied> {
         self.spawn_entity(
             (coord, Layer::Feature),
             entity_data! {
@@ -60,10 +82,11 @@ impl World {
                 solid: (),
                 opacity: 255,
             },
-        );
+        )?;
+        Ok(())
     }
 
-    pub fn spawn_door(&mut self, coord: Coord) {
+    pub fn spawn_door(&mut self, coord: Coord) -> Result<(), CellOccupied> {
         self.spawn_entity(
             (coord, Layer::Feature),
             entity_data! {
@@ -72,28 +95,75 @@ impl World {
                 solid: (),
                 opacity: 255,
             },
-        );
+        )?;
+        Ok(())
+    }
+
+    /// A door too narrow for heavy robots to squeeze through
+    pub fn spawn_narrow_door(&mut self, coord: Coord) -> Result<(), CellOccupied> {
+        self.spawn_entity(
+            (coord, Layer::Feature),
+            entity_data! {
+                tile: Tile::NarrowDoorClosed,
+                door_state: DoorState::Closed,
+                narrow_door: (),
+                solid: (),
+                opacity: 255,
+            },
+        )?;
+        Ok(())
+    }
+
+    /// A door which looks like part of the wall until it's found
+    pub fn spawn_secret_door(&mut self, coord: Coord) -> Result<(), CellOccupied> {
+        self.spawn_entity(
+            (coord, Layer::Feature),
+            entity_data! {
+                tile: Tile::Wall,
+                secret_door: (),
+                undiscovered: (),
+                solid: (),
+                opacity: 255,
+            },
+        )?;
+        Ok(())
     }
 
-    pub fn spawn_floor(&mut self, coord: Coord) {
+    pub fn spawn_locked_door(&mut self, coord: Coord, clearance: Clearance) -> Result<(), CellOccupied> {
+        self.spawn_entity(
+            (coord, Layer::Feature),
+            entity_data! {
+                tile: Tile::DoorLocked(clearance),
+                door_state: DoorState::Closed,
+                locked: clearance,
+                solid: (),
+                opacity: 255,
+            },
+        )?;
+        Ok(())
+    }
+
+    pub fn spawn_floor(&mut self, coord: Coord) -> Result<(), CellOccupied> {
         self.spawn_entity(
             (coord, Layer::Floor),
             entity_data! {
                 tile: Tile::Floor,
             },
-        );
+        )?;
+        Ok(())
     }
 
-    pub fn spawn_cave_floor(&mut self, coord: Coord) {
+    pub fn spawn_cave_floor(&mut self, coord: Coord) -> Result<(), CellOccupied> {
         self.spawn_entity(
             (coord, Layer::Floor),
             entity_data! {
                 tile: Tile::CaveFloor,
             },
-        );
+        )?;
+        Ok(())
     }
 
-    pub fn spawn_grass(&mut self, coord: Coord) {
+    pub fn spawn_grass(&mut self, coord: Coord) -> Result<(), CellOccupied> {
         self.spawn_entity(
             (coord, Layer::Feature),
             entity_data! {
@@ -101,46 +171,204 @@ impl World {
                 opacity: 128,
                 grass_state: GrassState::Normal,
             },
-        );
+        )?;
+        Ok(())
     }
 
-    pub fn spawn_water(&mut self, coord: Coord) {
+    pub fn spawn_water(&mut self, coord: Coord) -> Result<(), CellOccupied> {
         self.spawn_entity(
             (coord, Layer::Floor),
             entity_data! {
                 tile: Tile::Water,
+                water: (),
             },
-        );
+        )?;
+        Ok(())
+    }
+
+    pub fn spawn_chasm(&mut self, coord: Coord) -> Result<(), CellOccupied> {
+        self.spawn_entity(
+            (coord, Layer::Floor),
+            entity_data! {
+                tile: Tile::Chasm,
+                chasm: (),
+            },
+        )?;
+        Ok(())
+    }
+
+    pub fn spawn_bridge(&mut self, coord: Coord) -> Result<(), CellOccupied> {
+        self.spawn_entity(
+            (coord, Layer::Floor),
+            entity_data! {
+                tile: Tile::Bridge,
+            },
+        )?;
+        Ok(())
     }
 
-    pub fn spawn_stairs(&mut self, coord: Coord) {
+    pub fn spawn_stairs(&mut self, coord: Coord) -> Result<(), CellOccupied> {
         self.spawn_entity(
             (coord, Layer::Feature),
             entity_data! {
                 tile: Tile::Stairs,
                 stairs: (),
             },
-        );
+        )?;
+        Ok(())
+    }
+
+    pub fn spawn_elevator(&mut self, coord: Coord) -> Result<(), CellOccupied> {
+        self.spawn_entity(
+            (coord, Layer::Feature),
+            entity_data! {
+                tile: Tile::Elevator,
+                elevator: (),
+                light: Light {
+                    colour: Rgb24::new(0, 255, 127),
+                    vision_distance: vision_distance::Circle::new_squared(20),
+                    diminish: Rational { numerator: 1, denominator: 8 },
+                },
+            },
+        )?;
+        Ok(())
     }
 
-    pub fn spawn_reactor(&mut self, coord: Coord) {
+    pub fn spawn_reactor(&mut self, coord: Coord) -> Result<(), CellOccupied> {
         self.spawn_entity(
             (coord, Layer::Feature),
             entity_data! {
                 solid: (),
-                reactor: (),
                 character: (),
+                stationary: (),
                 explodes_on_death: (),
                 tile: Tile::Reactor,
-                armour: Armour::new(0),
-                hp: HitPoints::new_full(30)
+                reactor: Reactor::default(),
+                armour: Armour::new(1),
+                hp: HitPoints::new_full(45)
             },
-        );
+        )?;
+        Ok(())
+    }
+
+    pub fn spawn_shield_generator(&mut self, coord: Coord) -> Result<(), CellOccupied> {
+        self.spawn_entity(
+            (coord, Layer::Feature),
+            entity_data! {
+                solid: (),
+                shield_generator: (),
+                tile: Tile::ShieldGenerator,
+                armour: Armour::new(2),
+                hp: HitPoints::new_full(8),
+                light: Light {
+                    colour: Rgb24::new(0, 127, 255),
+                    vision_distance: vision_distance::Circle::new_squared(20),
+                    diminish: Rational { numerator: 1, denominator: 8 },
+                },
+            },
+        )?;
+        Ok(())
+    }
+
+    pub fn spawn_camera(&mut self, coord: Coord, facing: CardinalDirection) -> Result<(), CellOccupied> {
+        self.spawn_entity(
+            (coord, Layer::Feature),
+            entity_data! {
+                solid: (),
+                tile: Tile::Camera(facing),
+                camera: Camera::new(facing),
+                opacity: 255,
+                armour: Armour::new(1),
+                hp: HitPoints::new_full(3),
+            },
+        )?;
+        Ok(())
+    }
+
+    pub fn spawn_alarm_panel(&mut self, coord: Coord) -> Result<(), CellOccupied> {
+        self.spawn_entity(
+            (coord, Layer::Feature),
+            entity_data! {
+                solid: (),
+                alarm_panel: (),
+                tile: Tile::AlarmPanel,
+                opacity: 255,
+                armour: Armour::new(2),
+                hp: HitPoints::new_full(5),
+            },
+        )?;
+        Ok(())
+    }
+
+    pub fn spawn_terminal(&mut self, coord: Coord, id: LoreId) -> Result<(), CellOccupied> {
+        self.spawn_entity(
+            (coord, Layer::Feature),
+            entity_data! {
+                solid: (),
+                terminal: id,
+                tile: Tile::Terminal,
+                opacity: 255,
+            },
+        )?;
+        Ok(())
+    }
+
+    pub fn spawn_power_relay(&mut self, coord: Coord) -> Result<Entity, CellOccupied> {
+        self.spawn_entity(
+            (coord, Layer::Feature),
+            entity_data! {
+                solid: (),
+                power_relay: (),
+                tile: Tile::PowerRelay,
+                armour: Armour::new(1),
+                hp: HitPoints::new_full(6),
+            },
+        )
+    }
+
+    pub fn spawn_generator(&mut self, coord: Coord) -> Result<(), CellOccupied> {
+        self.spawn_entity(
+            (coord, Layer::Feature),
+            entity_data! {
+                solid: (),
+                generator: (),
+                tile: Tile::Generator,
+                armour: Armour::new(2),
+                hp: HitPoints::new_full(8),
+            },
+        )?;
+        Ok(())
+    }
+
+    pub fn spawn_server_rack(&mut self, coord: Coord) -> Result<(), CellOccupied> {
+        self.spawn_entity(
+            (coord, Layer::Feature),
+            entity_data! {
+                solid: (),
+                server_rack: (),
+                tile: Tile::ServerRack,
+                opacity: 255,
+            },
+        )?;
+        Ok(())
+    }
+
+    pub fn spawn_power_socket(&mut self, coord: Coord) -> Result<(), CellOccupied> {
+        self.spawn_entity(
+            (coord, Layer::Feature),
+            entity_data! {
+                solid: (),
+                power_socket: (),
+                tile: Tile::PowerSocket,
+                opacity: 255,
+            },
+        )?;
+        Ok(())
     }
 
     // Entities
 
-    pub fn spawn_player(&mut self, coord: Coord) -> Entity {
+    pub fn spawn_player(&mut self, coord: Coord) -> Result<Entity, CellOccupied> {
         self.spawn_entity(
             (coord, Layer::Character),
             entity_data! {
@@ -150,16 +378,17 @@ impl World {
                 armour: Armour::new(3),
                 hp: HitPoints::new_full(25),
                 vision: vision_distance::Circle::new(200),
+                // A faint glow so the player can see their surroundings without the flashlight
                 light: Light {
-                    colour: Rgb24::new_grey(200),
-                    vision_distance: vision_distance::Circle::new_squared(200),
+                    colour: Rgb24::new_grey(120),
+                    vision_distance: vision_distance::Circle::new_squared(16),
                     diminish: Rational {numerator: 1, denominator: 8},
                 },
             },
         )
     }
 
-    pub fn spawn_minibot(&mut self, coord: Coord) -> Entity {
+    pub fn spawn_minibot(&mut self, coord: Coord) -> Result<Entity, CellOccupied> {
         self.spawn_entity(
             (coord, Layer::Character),
             entity_data! {
@@ -169,7 +398,6 @@ impl World {
                 hp: HitPoints::new_full(3),
                 tile: Tile::Npc(NpcType::MiniBot),
                 npc: Npc {
-                    drop_chance: 5,
                     disposition: Disposition::Hostile,
                     npc_type: NpcType::MiniBot,
                     move_to: None,
@@ -179,7 +407,7 @@ impl World {
         )
     }
 
-    pub fn spawn_secbot(&mut self, coord: Coord) -> Entity {
+    pub fn spawn_secbot(&mut self, coord: Coord) -> Result<Entity, CellOccupied> {
         self.spawn_entity(
             (coord, Layer::Character),
             entity_data! {
@@ -189,7 +417,6 @@ impl World {
                 hp: HitPoints::new_full(5),
                 tile: Tile::Npc(NpcType::SecBot),
                 npc: Npc {
-                    drop_chance: 15,
                     disposition: Disposition::Hostile,
                     npc_type: NpcType::SecBot ,
                     move_to: None,
@@ -199,7 +426,7 @@ impl World {
         )
     }
 
-    pub fn spawn_robocop(&mut self, coord: Coord) -> Entity {
+    pub fn spawn_robocop(&mut self, coord: Coord) -> Result<Entity, CellOccupied> {
         self.spawn_entity(
             (coord, Layer::Character),
             entity_data! {
@@ -209,7 +436,6 @@ impl World {
                 hp: HitPoints::new_full(10),
                 tile: Tile::Npc(NpcType::RoboCop),
                 npc: Npc {
-                    drop_chance: 40,
                     disposition:Disposition::Hostile,
                     npc_type:NpcType::RoboCop,
                     move_to: None,
@@ -219,7 +445,7 @@ impl World {
         )
     }
 
-    pub fn spawn_doombot(&mut self, coord: Coord) -> Entity {
+    pub fn spawn_doombot(&mut self, coord: Coord) -> Result<Entity, CellOccupied> {
         let weapon_roll = crate::rng::roll_dice(1, 4);
         let weapon = match weapon_roll {
             0 => Some(Weapon::new_railgun()),
@@ -244,7 +470,6 @@ impl World {
                 hp: HitPoints::new_full(20),
                 tile: Tile::Npc(NpcType::DoomBot),
                 npc: Npc {
-                    drop_chance: 80,
                     disposition: Disposition::Hostile,
                     npc_type: NpcType::DoomBot,
                     move_to: None,
@@ -254,8 +479,119 @@ impl World {
         )
     }
 
+    pub fn spawn_repairbot(&mut self, coord: Coord) -> Result<Entity, CellOccupied> {
+        self.spawn_entity(
+            (coord, Layer::Character),
+            entity_data! {
+                damage: 1,
+                character: (),
+                armour: Armour::new(2),
+                hp: HitPoints::new_full(4),
+                tile: Tile::Npc(NpcType::RepairBot),
+                npc: Npc {
+                    disposition: Disposition::Afraid,
+                    npc_type: NpcType::RepairBot,
+                    move_to: None,
+                    weapon: None
+                },
+            },
+        )
+    }
+
+    pub fn spawn_shieldbot(&mut self, coord: Coord) -> Result<Entity, CellOccupied> {
+        self.spawn_entity(
+            (coord, Layer::Character),
+            entity_data! {
+                damage: 1,
+                character: (),
+                armour: Armour::new(5),
+                hp: HitPoints::new_full(6),
+                tile: Tile::Npc(NpcType::ShieldBot),
+                npc: Npc {
+                    disposition: Disposition::Hostile,
+                    npc_type: NpcType::ShieldBot,
+                    move_to: None,
+                    weapon: None
+                },
+            },
+        )
+    }
+
+    pub fn spawn_drone(&mut self, coord: Coord) -> Result<Entity, CellOccupied> {
+        self.spawn_entity(
+            (coord, Layer::Character),
+            entity_data! {
+                damage: 1,
+                character: (),
+                armour: Armour::new(1),
+                hp: HitPoints::new_full(2),
+                tile: Tile::Npc(NpcType::Drone),
+                npc: Npc {
+                    disposition: Disposition::Hostile,
+                    npc_type: NpcType::Drone,
+                    move_to: None,
+                    weapon: None
+                },
+            },
+        )
+    }
+
+    pub fn spawn_skimmer(&mut self, coord: Coord) -> Result<Entity, CellOccupied> {
+        self.spawn_entity(
+            (coord, Layer::Character),
+            entity_data! {
+                damage: 2,
+                character: (),
+                armour: Armour::new(3),
+                hp: HitPoints::new_full(6),
+                tile: Tile::Npc(NpcType::Skimmer),
+                npc: Npc {
+                    disposition: Disposition::Hostile,
+                    npc_type: NpcType::Skimmer,
+                    move_to: None,
+                    weapon: None
+                },
+            },
+        )
+    }
+
+    pub fn spawn_turret(&mut self, coord: Coord) -> Result<Entity, CellOccupied> {
+        let weapon = if crate::rng::range(0..=100) < 50 { Weapon::new_rifle() } else { Weapon::new_pistol() };
+
+        self.spawn_entity(
+            (coord, Layer::Character),
+            entity_data! {
+                character: (),
+                stationary: (),
+                armour: Armour::new(4),
+                hp: HitPoints::new_full(8),
+                tile: Tile::Npc(NpcType::Turret),
+                npc: Npc {
+                    disposition: Disposition::Hostile,
+                    npc_type: NpcType::Turret,
+                    move_to: None,
+                    weapon: Some(weapon)
+                },
+            },
+        )
+    }
+
+    pub fn spawn_npc(&mut self, coord: Coord, npc_type: NpcType) -> Result<Entity, CellOccupied> {
+        match npc_type {
+            NpcType::MiniBot => self.spawn_minibot(coord),
+            NpcType::SecBot => self.spawn_secbot(coord),
+            NpcType::RoboCop => self.spawn_robocop(coord),
+            NpcType::DoomBot => self.spawn_doombot(coord),
+            NpcType::Turret => self.spawn_turret(coord),
+            NpcType::RepairBot => self.spawn_repairbot(coord),
+            NpcType::ShieldBot => self.spawn_shieldbot(coord),
+            NpcType::Drone => self.spawn_drone(coord),
+            NpcType::Skimmer => self.spawn_skimmer(coord),
+        }
+    }
+
     // Items
-    pub fn spawn_weapon(&mut self, coord: Coord, ranged_weapon: WeaponType) {
+    pub fn spawn_weapon(&mut self, coord: Coord, ranged_weapon: WeaponType) -> Result<(), CellOccupied> {
         self.spawn_entity(
             (coord, Layer::Item),
             entity_data! {
@@ -263,20 +599,51 @@ impl World {
                 item: Item::Weapon(ranged_weapon),
                 weapon: ranged_weapon.new_weapon(),
             },
-        );
+        )?;
+        Ok(())
     }
 
-    pub fn spawn_medkit(&mut self, coord: Coord) {
+    pub fn spawn_loot(&mut self, coord: Coord, loot: Loot) -> Result<(), CellOccupied> {
+        match loot {
+            Loot::Weapon(weapon) => self.spawn_weapon(coord, weapon),
+            Loot::Medkit => self.spawn_medkit(coord),
+            Loot::Credit(value) => self.spawn_credit(coord, value),
+        }
+    }
+
+    pub fn spawn_medkit(&mut self, coord: Coord) -> Result<(), CellOccupied> {
         self.spawn_entity(
             (coord, Layer::Item),
             entity_data! {
                 tile: Tile::Medkit,
                 item: Item::Medkit,
             },
-        );
+        )?;
+        Ok(())
+    }
+
+    pub fn spawn_keycard(&mut self, coord: Coord, clearance: Clearance) -> Result<(), CellOccupied> {
+        self.spawn_entity(
+            (coord, Layer::Item),
+            entity_data! {
+                tile: Tile::Keycard(clearance),
+                item: Item::Keycard(clearance),
+            },
+        )?;
+        Ok(())
+    }
+
+    pub fn spawn_dog_tags(&mut self, coord: Coord) -> Result<Entity, CellOccupied> {
+        self.spawn_entity(
+            (coord, Layer::Item),
+            entity_data! {
+                tile: Tile::DogTags,
+                item: Item::DogTags,
+            },
+        )
     }
 
-    pub fn spawn_upgrade(&mut self, coord: Coord) {
+    pub fn spawn_upgrade(&mut self, coord: Coord) -> Result<(), CellOccupied> {
         self.spawn_entity(
             (coord, Layer::Feature),
             entity_data! {
@@ -285,10 +652,11 @@ impl World {
                 tile: Tile::Upgrade,
                 item: Item::Medkit,
             },
-        );
+        )?;
+        Ok(())
     }
 
-    pub fn spawn_credit(&mut self, coord: Coord, value: u32) {
+    pub fn spawn_credit(&mut self, coord: Coord, value: u32) -> Result<(), CellOccupied> {
         let tile = if value == 1 {
             Tile::Credit1
         } else if value == 2 {
@@ -303,7 +671,8 @@ impl World {
                 tile,
                 item: Item::Credit(value),
             },
-        );
+        )?;
+        Ok(())
     }
 
     // Effects
@@ -21,11 +21,16 @@ impl VisibleWorld for World {
     }
 
     fn for_each_light_by_coord<F: FnMut(Coord, &Light<Self::VisionDistance>)>(&self, mut f: F) {
-        self.components.light.iter().for_each(|(entity, light)| {
-            if let Some((c, l)) = self.spatial_table.coord_of(entity).map(|coord| (coord, light)) {
-                f(c, l)
-            }
-        });
+        self.components.light.iter().filter(|&(entity, _)| self.is_powered(entity)).for_each(
+            |(entity, light)| {
+                if let Some((c, l)) = self.spatial_table.coord_of(entity).map(|coord| (coord, light)) {
+                    f(c, l)
+                }
+            },
+        );
+        for (coord, light) in self.flashlight_beams() {
+            f(coord, &light);
+        }
     }
 }
 
@@ -43,12 +48,21 @@ pub struct RealTimeEntity {
 pub struct VisibleCellData {
     pub tiles: LayerTable<Option<Tile>>,
     pub realtime: Vec<RealTimeEntity>,
+    /// False if the generator powering this cell has been destroyed
+    pub powered: bool,
+    /// True if the door here was hidden in the wall until the player found it
+    #[serde(default)]
+    pub secret_door: bool,
 }
 
 impl VisibleCellData {
     pub fn update(&mut self, world: &World, coord: Coord) {
         let layers = world.spatial_table.layers_at_checked(coord);
         self.tiles = layers.option_and_then(|&entity| world.components.tile.get(entity).cloned());
+        self.powered = world.is_powered_at(coord);
+        self.secret_door = layers.feature.map_or(false, |feature| {
+            world.components.secret_door.contains(feature) && !world.components.undiscovered.contains(feature)
+        });
 
         let tile_component = &world.components.tile;
         let spatial_table = &world.spatial_table;